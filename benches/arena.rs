@@ -0,0 +1,75 @@
+//! Compares the hash-consed arena with plain expression trees on the expansion of (a + b + c) ** 8.
+//!
+//! Run with `cargo bench --bench arena`.
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use ruth::{arena::Arena, Addition, BinaryOperation, Expressions, Multiplication, WrappedReal};
+
+const POWER: u32 = 8;
+const RUNS: u32 = 20;
+
+// Every term of the expansion as a product of its variables in sorted order, built without sharing
+fn expansion() -> Expressions {
+    let mut terms: Vec<Vec<&str>> = vec![vec![]];
+
+    for _ in 0..POWER {
+        terms = terms.iter()
+            .flat_map(|term| ["a", "b", "c"].map(|variable| {
+                let mut term = term.clone();
+                term.push(variable);
+                term.sort();
+                term
+            }))
+            .collect();
+    }
+
+    terms.iter()
+        .map(|term| term.iter()
+            .map(|variable| WrappedReal::new_variable(variable).into())
+            .reduce(|product: Expressions, factor| Multiplication::new(product, factor).into())
+            .unwrap())
+        .reduce(|sum, term| Addition::new(sum, term).into())
+        .unwrap()
+}
+
+fn tree_size(expression: &Expressions) -> usize {
+    1 + expression.get_children().iter().map(tree_size).sum::<usize>()
+}
+
+fn measure<T>(name: &str, mut f: impl FnMut() -> T) {
+    let mut total = Duration::ZERO;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(f());
+        total += start.elapsed();
+    }
+
+    println!("{name:<40} {:>12.3?}", total / RUNS);
+}
+
+fn main() {
+    let left = expansion();
+    let right = expansion();
+
+    let mut arena = Arena::new();
+    let left_id = arena.intern(&left);
+    let right_id = arena.intern(&right);
+    assert_eq!(left_id, right_id);
+    assert!(arena.to_expression(left_id) == left);
+
+    println!("(a + b + c) ** {POWER}");
+    println!("{:<40} {:>12}", "tree nodes", tree_size(&left));
+    println!("{:<40} {:>12}", "arena nodes", arena.len());
+    println!();
+
+    measure("build Expressions", expansion);
+    measure("build and intern", || Arena::new().intern(&expansion()));
+    measure("intern into a filled arena", || arena.intern(&right));
+    measure("Expressions equality", || left == right);
+    measure("NodeId equality", || left_id == right_id);
+    measure("tree size of Expressions", || tree_size(&left));
+    measure("cached size of node", || arena.size(left_id));
+    measure("rebuild Expressions from arena", || arena.to_expression(left_id));
+}
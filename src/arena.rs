@@ -0,0 +1,401 @@
+//! Hash-consed storage of expressions, where identical subexpressions share one node.
+
+use std::{
+    sync::OnceLock,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use dashu_float::DBig;
+
+use crate::{
+    operations::Operation, Addition, Complex, BinaryOperation, Derivative, Division, Exponentiation, Expressions, FunctionCall, InnerExpressions,
+    Integral, Multiplication, Natural, OperationTrait, Product, Rational, Real, Subtraction, Sum, Types, Wrapper, Zahl,
+};
+
+/// Index of a node in its arena, equal ids mean structurally equal expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+/// Number type of a value node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberType {
+    Natural,
+    Zahl,
+    Rational,
+    Real,
+    Complex,
+}
+
+/// Constant of a value node, reals are stored by their bits or digits to be hashable
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Constant {
+    Natural(u32),
+    Zahl(i32),
+    Rational(i64, i64),
+    Real(u64),
+    Decimal(Decimal),
+    ExactComplex(i64, i64, i64, i64),
+    ApproximateComplex(u64, u64),
+}
+
+/// Decimal of a constant, hashed by its normalized significand and exponent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decimal(pub DBig);
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.repr().significand().hash(state);
+        self.0.repr().exponent().hash(state);
+    }
+}
+
+/// Operation of a node together with its non expression fields
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OperationKind {
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    Exponentiation,
+    Derivative(String),
+    Integral { variable: String, definite: bool },
+    FunctionCall(String),
+    Sum(String),
+    Product(String),
+}
+
+/// Node of the arena, children are ids of nodes added before it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Node {
+    Constant(Constant),
+    Variable(NumberType, String),
+    Wrapped(NumberType, NodeId),
+    Operation(OperationKind, Vec<NodeId>),
+}
+
+// Data derived from a node once, when it is added
+struct NodeData {
+    hash: u64,
+    size: u64,
+    type_name: OnceLock<Option<String>>,
+}
+
+/// Interner of expression nodes
+#[derive(Default)]
+pub struct Arena {
+    nodes: Vec<Node>,
+    data: Vec<NodeData>,
+    // Ids of the nodes by their structural hash, equal hashes of different nodes share the entry
+    ids: HashMap<u64, Vec<NodeId>>,
+}
+
+fn wrapper<T>(wrapper: &Wrapper<T>, number_type: NumberType, constant: impl Fn(&T) -> Constant) -> Result<Node, Expressions> {
+    match wrapper {
+        Wrapper::Constant(value) => Ok(Node::Constant(constant(value))),
+        Wrapper::Variable(name) => Ok(Node::Variable(number_type, name.clone())),
+        Wrapper::Expression(expression) => Err(expression.clone()),
+    }
+}
+
+fn number_type(types: &Types) -> NumberType {
+    match types {
+        Types::Natural(_) => NumberType::Natural,
+        Types::Zahl(_) => NumberType::Zahl,
+        Types::Rational(_) => NumberType::Rational,
+        Types::Real(_) => NumberType::Real,
+        Types::Complex(_) => NumberType::Complex,
+    }
+}
+
+fn operation_kind(operation: &Operation) -> OperationKind {
+    match operation {
+        Operation::Addition(_) => OperationKind::Addition,
+        Operation::Subtraction(_) => OperationKind::Subtraction,
+        Operation::Multiplication(_) => OperationKind::Multiplication,
+        Operation::Division(_) => OperationKind::Division,
+        Operation::Exponentiation(_) => OperationKind::Exponentiation,
+        Operation::Derivative(derivative) => OperationKind::Derivative(derivative.variable.clone()),
+        Operation::Integral(integral) => OperationKind::Integral {
+            variable: integral.variable.clone(),
+            definite: integral.bounds.is_some(),
+        },
+        Operation::FunctionCall(call) => OperationKind::FunctionCall(call.name.clone()),
+        Operation::Sum(sum) => OperationKind::Sum(sum.index.clone()),
+        Operation::Product(product) => OperationKind::Product(product.index.clone()),
+    }
+}
+
+fn build_operation(kind: &OperationKind, mut children: Vec<Expressions>) -> Expressions {
+    let mut next = || children.remove(0);
+
+    match kind {
+        OperationKind::Addition => Addition::new(next(), next()).into(),
+        OperationKind::Subtraction => Subtraction::new(next(), next()).into(),
+        OperationKind::Multiplication => Multiplication::new(next(), next()).into(),
+        OperationKind::Division => Division::new(next(), next()).into(),
+        OperationKind::Exponentiation => Exponentiation::new(next(), next()).into(),
+        OperationKind::Derivative(variable) => Derivative::new(next(), variable).into(),
+        OperationKind::Integral { variable, definite: false } => Integral::new(next(), variable).into(),
+        OperationKind::Integral { variable, definite: true } => Integral::new_definite(next(), variable, next(), next()).into(),
+        OperationKind::FunctionCall(name) => FunctionCall::new(name, children).into(),
+        OperationKind::Sum(index) => {
+            let body = next();
+            Sum::new(index, next(), next(), body).into()
+        }
+        OperationKind::Product(index) => {
+            let body = next();
+            Product::new(index, next(), next(), body).into()
+        }
+    }
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Hash of the node with children replaced by their structural hashes, so it does not depend on the ids
+    fn structural_hash(&self, node: &Node) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        std::mem::discriminant(node).hash(&mut hasher);
+
+        match node {
+            Node::Constant(constant) => constant.hash(&mut hasher),
+            Node::Variable(number_type, name) => (number_type, name).hash(&mut hasher),
+            Node::Wrapped(number_type, expression) => (number_type, self.hash(*expression)).hash(&mut hasher),
+            Node::Operation(kind, children) => {
+                (kind, children.len()).hash(&mut hasher);
+                for child in children {
+                    self.hash(*child).hash(&mut hasher);
+                }
+            }
+        }
+
+        hasher.finish()
+    }
+
+    /// Id of the node, adding it if no equal node exists yet
+    pub fn insert(&mut self, node: Node) -> NodeId {
+        let hash = self.structural_hash(&node);
+
+        if let Some(id) = self.ids.get(&hash).and_then(|ids| ids.iter().find(|id| *self.node(**id) == node)) {
+            return *id;
+        }
+
+        let size = match &node {
+            Node::Constant(_) | Node::Variable(..) => 1,
+            Node::Wrapped(_, expression) => self.size(*expression),
+            Node::Operation(_, children) => children.iter().fold(1u64, |size, child| size.saturating_add(self.size(*child))),
+        };
+
+        let id = NodeId(self.nodes.len() as u32);
+        self.nodes.push(node);
+        self.data.push(NodeData { hash, size, type_name: OnceLock::new() });
+        self.ids.entry(hash).or_default().push(id);
+
+        id
+    }
+
+    /// Adds an expression tree, subtrees already in the arena are reused
+    pub fn intern(&mut self, expression: &Expressions) -> NodeId {
+        let mut interned = HashMap::new();
+        self.intern_shared(expression, &mut interned)
+    }
+
+    // Subtrees shared by Arc are only visited once
+    fn intern_shared(&mut self, expression: &Expressions, interned: &mut HashMap<*const InnerExpressions, NodeId>) -> NodeId {
+        let key = std::sync::Arc::as_ptr(&expression.0);
+
+        if let Some(id) = interned.get(&key) {
+            return *id;
+        }
+
+        let node = match expression.0.as_ref() {
+            InnerExpressions::Type(types) => {
+                let node = match types {
+                    Types::Natural(natural) => wrapper(natural, NumberType::Natural, |value| Constant::Natural(value.get())),
+                    Types::Zahl(zahl) => wrapper(zahl, NumberType::Zahl, |value| Constant::Zahl(value.get())),
+                    Types::Rational(rational) => wrapper(rational, NumberType::Rational, |value| {
+                        Constant::Rational(value.numerator(), value.denominator())
+                    }),
+                    // 0 and -0 are equal, so they have to be the same node
+                    Types::Real(real) => wrapper(real, NumberType::Real, |value| match value {
+                        Real::Double(double) => Constant::Real((double + 0.0).to_bits()),
+                        Real::Decimal(decimal) => Constant::Decimal(Decimal(decimal.clone())),
+                    }),
+                    Types::Complex(complex) => wrapper(complex, NumberType::Complex, |value| match value {
+                        Complex::Exact(real, imaginary) => Constant::ExactComplex(
+                            real.numerator(), real.denominator(), imaginary.numerator(), imaginary.denominator(),
+                        ),
+                        Complex::Approximate(real, imaginary) => {
+                            Constant::ApproximateComplex((real.get() + 0.0).to_bits(), (imaginary.get() + 0.0).to_bits())
+                        }
+                    }),
+                };
+
+                node.unwrap_or_else(|expression| Node::Wrapped(number_type(types), self.intern_shared(&expression, interned)))
+            }
+            InnerExpressions::Operation(operation) => {
+                let children = operation.get_children().iter().map(|child| self.intern_shared(child, interned)).collect();
+                Node::Operation(operation_kind(operation), children)
+            }
+        };
+
+        let id = self.insert(node);
+        interned.insert(key, id);
+        id
+    }
+
+    /// Expression tree of the node, shared nodes become shared subtrees
+    pub fn to_expression(&self, id: NodeId) -> Expressions {
+        let mut built = HashMap::new();
+        self.build(id, &mut built)
+    }
+
+    fn build(&self, id: NodeId, built: &mut HashMap<NodeId, Expressions>) -> Expressions {
+        if let Some(expression) = built.get(&id) {
+            return expression.clone();
+        }
+
+        let expression: Expressions = match self.node(id) {
+            Node::Constant(Constant::Natural(value)) => Types::Natural(Wrapper::Constant(Natural::new(*value))).into(),
+            Node::Constant(Constant::Zahl(value)) => Types::Zahl(Wrapper::Constant(Zahl::new(*value))).into(),
+            Node::Constant(Constant::Rational(numerator, denominator)) => {
+                Types::Rational(Wrapper::Constant(Rational::new(*numerator, *denominator))).into()
+            }
+            Node::Constant(Constant::Real(bits)) => Types::Real(Wrapper::Constant(Real::new(f64::from_bits(*bits)))).into(),
+            Node::Constant(Constant::Decimal(Decimal(decimal))) => Types::Real(Wrapper::Constant(Real::Decimal(decimal.clone()))).into(),
+            Node::Constant(Constant::ExactComplex(real_numerator, real_denominator, imaginary_numerator, imaginary_denominator)) => {
+                let complex = Complex::Exact(
+                    Rational::new(*real_numerator, *real_denominator),
+                    Rational::new(*imaginary_numerator, *imaginary_denominator),
+                );
+                Types::Complex(Wrapper::Constant(complex)).into()
+            }
+            Node::Constant(Constant::ApproximateComplex(real, imaginary)) => {
+                Types::Complex(Wrapper::Constant(Complex::approximate(f64::from_bits(*real), f64::from_bits(*imaginary)))).into()
+            }
+            Node::Variable(number_type, name) => wrap(*number_type, Ok(name.clone())),
+            Node::Wrapped(number_type, expression) => {
+                let expression = self.build(*expression, built);
+                wrap(*number_type, Err(expression))
+            }
+            Node::Operation(kind, children) => {
+                let children = children.iter().map(|child| self.build(*child, built)).collect();
+                build_operation(kind, children)
+            }
+        };
+
+        built.insert(id, expression.clone());
+        expression
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// Structural hash, equal for equal expressions in any arena
+    pub fn hash(&self, id: NodeId) -> u64 {
+        self.data[id.0 as usize].hash
+    }
+
+    /// Number of nodes of the expression as a tree, counting shared nodes every time
+    pub fn size(&self, id: NodeId) -> u64 {
+        self.data[id.0 as usize].size
+    }
+
+    /// Type of the solved expression, None if solving fails. Computed on first use
+    pub fn type_name(&self, id: NodeId) -> Option<&str> {
+        self.data[id.0 as usize].type_name
+            .get_or_init(|| self.to_expression(id).solve().ok().map(|types| types.get_type().to_string()))
+            .as_deref()
+    }
+}
+
+// Variable or expression wrapper of the number type
+fn wrap(number_type: NumberType, name_or_expression: Result<String, Expressions>) -> Expressions {
+    macro_rules! wrapped {
+        ($variant:ident) => {
+            match name_or_expression {
+                Ok(name) => Types::$variant(Wrapper::Variable(name)).into(),
+                Err(expression) => Types::$variant(Wrapper::Expression(expression)).into(),
+            }
+        };
+    }
+
+    match number_type {
+        NumberType::Natural => wrapped!(Natural),
+        NumberType::Zahl => wrapped!(Zahl),
+        NumberType::Rational => wrapped!(Rational),
+        NumberType::Real => wrapped!(Real),
+        NumberType::Complex => wrapped!(Complex),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    #[test]
+    fn equal_subexpressions_share_a_node() {
+        let mut arena = Arena::new();
+        let id = arena.intern(&parse("(a + b) * (a + b)").unwrap());
+
+        let Node::Operation(OperationKind::Multiplication, children) = arena.node(id) else {
+            panic!("expected a multiplication");
+        };
+        assert_eq!(children[0], children[1]);
+        assert_eq!(arena.len(), 4);
+        assert_eq!(arena.size(id), 7);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut arena = Arena::new();
+
+        for expression in ["1/2 * x ** 3 - sin(y)", "sum(k, 1, n, k ** 2)", "exp(x) + 3 * i", "0.25 + (-2)"] {
+            let expression = parse(expression).unwrap();
+            let id = arena.intern(&expression);
+
+            assert!(arena.to_expression(id) == expression, "{expression}");
+        }
+    }
+
+    #[test]
+    fn hashes_do_not_depend_on_the_arena() {
+        let mut first = Arena::new();
+        let mut second = Arena::new();
+        second.intern(&parse("y * z + 7").unwrap());
+
+        let expression = parse("(x + 1) ** 2").unwrap();
+        let (first_id, second_id) = (first.intern(&expression), second.intern(&expression));
+
+        assert_ne!(first_id, second_id);
+        assert_eq!(first.hash(first_id), second.hash(second_id));
+        let other = first.intern(&parse("(x + 2) ** 2").unwrap());
+        assert_ne!(first.hash(first_id), first.hash(other));
+    }
+
+    #[test]
+    fn decimals_are_interned_by_value() {
+        let decimal = |digits: &str| Expressions::from(Types::Real(Wrapper::Constant(Real::Decimal(digits.parse().unwrap()))));
+
+        let mut arena = Arena::new();
+        let id = arena.intern(&decimal("3.14159265358979323846"));
+
+        assert_eq!(arena.intern(&decimal("3.14159265358979323846")), id);
+        assert_ne!(arena.intern(&decimal("3.14159265358979323847")), id);
+        assert!(arena.to_expression(id) == decimal("3.14159265358979323846"));
+    }
+}
@@ -0,0 +1,72 @@
+use ruth::{latex, parser, rules, Error, Expressions, TypeTrait, Types};
+
+// Exit codes of the one-shot subcommands
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_PARSE: i32 = 3;
+pub const EXIT_EVALUATION: i32 = 4;
+
+pub const SUBCOMMANDS: [&str; 5] = ["eval", "numeric", "simplify", "rules", "latex"];
+
+const USAGE: &str = "Usage: ruth [--script <file> | eval | numeric | simplify | rules | latex <expression>]";
+
+fn parse(args: &[String]) -> Result<Expressions, i32> {
+    if args.is_empty() {
+        return Err(usage());
+    }
+
+    parser::parse(&args.join(" ")).map_err(|error| {
+        eprintln!("Invalid expression: {error}");
+        EXIT_PARSE
+    })
+}
+
+// Value of the expression, an error if it still contains free variables
+fn evaluate(expression: &Expressions, solve: fn(&Expressions) -> Result<Types, Error>) -> Result<Types, Error> {
+    let result = solve(expression)?;
+
+    if !result.is_value() {
+        if let Some(name) = expression.variables().into_iter().find(|name| expression.contains_variable(name)) {
+            return Err(Error::unbound_variable(name));
+        }
+    }
+
+    Ok(result)
+}
+
+// Runs a subcommand on the expression given by the remaining arguments,
+// returns the exit code
+pub fn run(subcommand: &str, args: &[String]) -> i32 {
+    let expression = match parse(args) {
+        Ok(expression) => expression,
+        Err(code) => return code,
+    };
+
+    match subcommand {
+        "eval" | "numeric" => {
+            let solve = if subcommand == "eval" { Expressions::solve } else { Expressions::numeric };
+
+            match evaluate(&expression, solve) {
+                Ok(result) => println!("{result}"),
+                Err(error) => {
+                    eprintln!("Could not evaluate: {error}");
+                    return EXIT_EVALUATION;
+                }
+            }
+        },
+        "simplify" => println!("{}", rules::simplify(&expression)),
+        "rules" => {
+            for (i, rule) in rules::find_all_rules(&expression, &[]).iter().enumerate() {
+                println!("{i}: {rule}");
+            }
+        },
+        "latex" => println!("{}", latex::to_latex(&expression)),
+        _ => return usage(),
+    }
+
+    0
+}
+
+pub fn usage() -> i32 {
+    eprintln!("{USAGE}");
+    EXIT_USAGE
+}
@@ -0,0 +1,589 @@
+//! Commands of the interactive mode and of scripts.
+
+use crate::{definitions::Definition, dot, functions, json, latex, parser, rules, session, soundness, tree, Derivative, Error, Expressions, FunctionCall, Integral, Product, State, Sum};
+
+/// Runs a command on the state, errors are reported by the caller
+pub type Execute = fn(&mut State, &[&str]) -> Result<(), Error>;
+
+/// Command of the interactive mode and of scripts
+pub struct Command {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub usage: &'static str,
+
+    pub execute: Execute,
+}
+
+pub static HELP_COMMAND: Command = Command {
+    name: "help",
+    description: "Prints this help page",
+    usage: "",
+
+    execute: |_state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        println!("Commands:");
+        for command in COMMANDS.iter() {
+            let start = format!("{} {}", command.name, command.usage);
+            println!("    {:20}: {}", start, command.description);
+        }
+
+        Ok(())
+    },
+};
+
+static HISTORY_COMMAND: Command = Command {
+    name: "history",
+    description: "Prints all equations history",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        println!("History:");
+        for step in state.history.iter() {
+            match &step.rule {
+                Some(rule) => println!("{:40} ({})", state.format(&step.equation), rule),
+                None => println!("{}", state.format(&step.equation)),
+            }
+        }
+
+        Ok(())
+    },
+};
+
+static CHILDREN_COMMAND: Command = Command {
+    name: "children",
+    description: "Prints current children or select child by index",
+    usage: "[<index> | top]",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let children = state.selection().get_children();
+
+        if let Some(index) = args.first() {
+            if index == &"top" {
+                state.selection_path.clear();
+                return Ok(());
+            }
+
+            let index = index.parse::<usize>().map_err(|error| Error::command(format!("Invalid index: {error}")))?;
+            if index >= children.len() {
+                return Err(Error::command(format!("Index: {index} out of range: {}", children.len())));
+            }
+
+            state.selection_path.push(index);
+        } else {
+            println!("Current children");
+            for (i, child) in children.iter().enumerate() {
+                println!("{i}: {}", state.format(child));
+            }
+        }
+
+        Ok(())
+    }
+};
+
+static TYPE_COMMAND: Command = Command {
+    name: "type",
+    description: "Prints current equation with types",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        println!("{:#}", state.selection());
+        Ok(())
+    }
+};
+
+static TREE_COMMAND: Command = Command {
+    name: "tree",
+    description: "Prints the selection as tree with types",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        print!("{}", tree::print_tree(&state.selection()));
+        Ok(())
+    }
+};
+
+static RULES_COMMAND: Command = Command {
+    name: "rules",
+    description: "Prints all rules",
+    usage: "[<index>]",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let rules = rules::find_all_rules(&state.selection(), &state.definitions);
+
+        if let Some(index) = args.first() {
+            let index = index.parse::<usize>().map_err(|error| Error::command(format!("Invalid index: {error}")))?;
+            if index >= rules.len() {
+                return Err(Error::command(format!("Index: {index} out of range: {}", rules.len())));
+            }
+
+            println!("Applying rule: {}", rules[index]);
+            state.apply(rules[index].result(), rules[index].name);
+        } else {
+            println!("Current applicable rules");
+            for (i, rule) in rules.iter().enumerate() {
+                println!("{i}: {}", rule);
+            }
+        }
+
+        Ok(())
+    }
+};
+
+static CHECK_COMMAND: Command = Command {
+    name: "check",
+    description: "Checks numerically that all rules applicable in the equation keep its value",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        let (matches, unsound) = soundness::check(&state.current, &state.definitions);
+
+        for rule in unsound.iter() {
+            println!("{rule}");
+        }
+
+        println!("{} of {matches} rule matches keep the value", matches - unsound.len());
+        Ok(())
+    }
+};
+
+static DIFF_COMMAND: Command = Command {
+    name: "diff",
+    description: "Differentiates the selection by a variable",
+    usage: "<variable>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let variable = args.first().ok_or_else(|| Error::command("Missing variable"))?;
+
+        let derivative = Derivative::new(state.selection(), variable);
+        state.apply(derivative.into(), &format!("Differentiate by {variable}"));
+        Ok(())
+    }
+};
+
+fn parse_bounds(lower: &str, upper: &str) -> Result<(Expressions, Expressions), Error> {
+    match (parser::parse(lower), parser::parse(upper)) {
+        (Ok(lower), Ok(upper)) => Ok((lower, upper)),
+        (Err(error), _) | (_, Err(error)) => Err(Error::parse(format!("Invalid bound: {error}"))),
+    }
+}
+
+static INTEGRATE_COMMAND: Command = Command {
+    name: "int",
+    description: "Integrates the selection by a variable",
+    usage: "<variable> [<lower> <upper>]",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let integral = match args {
+            [variable] => Integral::new(state.selection(), variable),
+            [variable, lower, upper] => {
+                let (lower, upper) = parse_bounds(lower, upper)?;
+                Integral::new_definite(state.selection(), variable, lower, upper)
+            },
+            _ => return Err(Error::command("Expected a variable and optionally two bounds")),
+        };
+
+        let rule = format!("Integrate by {}", integral.variable);
+        state.apply(integral.into(), &rule);
+        Ok(())
+    }
+};
+
+static SUM_COMMAND: Command = Command {
+    name: "sum",
+    description: "Sums the selection over an index",
+    usage: "<index> <from> <to>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let [index, from, to] = args else {
+            return Err(Error::command("Expected an index and two bounds"));
+        };
+
+        let (from, to) = parse_bounds(from, to)?;
+        state.apply(Sum::new(index, from, to, state.selection()).into(), &format!("Sum over {index}"));
+        Ok(())
+    }
+};
+
+static PRODUCT_COMMAND: Command = Command {
+    name: "prod",
+    description: "Multiplies the selection over an index",
+    usage: "<index> <from> <to>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let [index, from, to] = args else {
+            return Err(Error::command("Expected an index and two bounds"));
+        };
+
+        let (from, to) = parse_bounds(from, to)?;
+        state.apply(Product::new(index, from, to, state.selection()).into(), &format!("Product over {index}"));
+        Ok(())
+    }
+};
+
+static CALL_COMMAND: Command = Command {
+    name: "call",
+    description: "Applies a function to the selection",
+    usage: "<function>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let name = args.first().ok_or_else(|| Error::command("Missing function"))?;
+
+        if functions::find_function(name).is_none() {
+            println!("Available functions:");
+            for function in functions::FUNCTIONS.iter() {
+                println!("    {:20}: {}", function.name, function.description);
+            }
+            return Err(Error::command(format!("Unknown function: {name}")));
+        }
+
+        state.apply(FunctionCall::new(name, vec![state.selection()]).into(), &format!("Apply {name}"));
+        Ok(())
+    }
+};
+
+static SIMPLIFY_COMMAND: Command = Command {
+    name: "simplify",
+    description: "Simplifies the selection with all terminating rules",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        let result = rules::simplify(&state.selection());
+        state.apply(result, "Simplify");
+        Ok(())
+    }
+};
+
+static NUMERIC_COMMAND: Command = Command {
+    name: "numeric",
+    description: "Evaluates the selection to a real number, including constants like pi",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        let result = state.selection().numeric_with_precision(state.precision)?;
+        state.apply(result.into(), "Numeric evaluation");
+        Ok(())
+    }
+};
+
+static LOAD_COMMAND: Command = Command {
+    name: "load",
+    description: "Replaces the equation",
+    usage: "<expression>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let equation = parser::parse(&args.join(" ")).map_err(|error| Error::parse(format!("Invalid expression: {error}")))?;
+
+        state.load(equation);
+        Ok(())
+    }
+};
+
+static LET_COMMAND: Command = Command {
+    name: "let",
+    description: "Defines a constant or function",
+    usage: "<name>[(<params>)] = <expression>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let definition = Definition::parse(&args.join(" ")).map_err(|error| Error::parse(format!("Invalid definition: {error}")))?;
+
+        println!("Defined: {}", definition);
+        state.definitions.retain(|other| other.name != definition.name);
+        state.definitions.push(definition);
+        Ok(())
+    }
+};
+
+static DEFS_COMMAND: Command = Command {
+    name: "defs",
+    description: "Prints all definitions",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        println!("Definitions:");
+        for definition in state.definitions.iter() {
+            println!("{}", definition);
+        }
+
+        Ok(())
+    }
+};
+
+static DISPLAY_COMMAND: Command = Command {
+    name: "display",
+    description: "Prints only needed or all parentheses",
+    usage: "minimal | verbose",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        match args.first() {
+            Some(&"minimal") => state.verbose = false,
+            Some(&"verbose") => state.verbose = true,
+            _ => return Err(Error::command("Expected minimal or verbose")),
+        }
+
+        Ok(())
+    }
+};
+
+static PRECISION_COMMAND: Command = Command {
+    name: "precision",
+    description: "Computes reals with the number of decimal digits, 0 for f64",
+    usage: "[<digits>]",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let Some(digits) = args.first() else {
+            match state.precision {
+                0 => println!("f64"),
+                digits => println!("{digits} digits"),
+            }
+
+            return Ok(());
+        };
+
+        state.precision = digits.parse().map_err(|_| Error::command(format!("Expected a number of digits, got {digits}")))?;
+
+        Ok(())
+    }
+};
+
+static RENDER_COMMAND: Command = Command {
+    name: "render",
+    description: "Prints the equation inline or in two dimensions",
+    usage: "inline | 2d",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        match args.first() {
+            Some(&"inline") => state.render_2d = false,
+            Some(&"2d") => state.render_2d = true,
+            _ => return Err(Error::command("Expected inline or 2d")),
+        }
+
+        Ok(())
+    }
+};
+
+static LATEX_COMMAND: Command = Command {
+    name: "latex",
+    description: "Prints the selection as LaTeX",
+    usage: "",
+
+    execute: |state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        println!("{}", latex::to_latex(&state.selection()));
+        Ok(())
+    }
+};
+
+static EXPORT_COMMAND: Command = Command {
+    name: "export",
+    description: "Writes the history or equation to a file",
+    usage: "latex <file> | dot <file> [history] | json <file>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let (format, file, history) = match args {
+            [format, file] => (*format, *file, false),
+            [format, file, "history"] => (*format, *file, true),
+            _ => return Err(Error::command("Expected a format and a file")),
+        };
+
+        let content = match format {
+            "latex" | "json" if history => return Err(Error::command("Only dot exports take the history option")),
+            "latex" => latex::history_to_latex(&state.history),
+            "dot" if history => dot::history_to_dot(&state.history),
+            "dot" => dot::to_dot(&state.current),
+            "json" => json::to_json(&state.current),
+            _ => return Err(Error::command(format!("Unknown format: {format}"))),
+        };
+
+        std::fs::write(file, content).map_err(|error| Error::io(format!("Could not write {file}: {error}")))?;
+        println!("Exported to {file}");
+        Ok(())
+    }
+};
+
+static IMPORT_COMMAND: Command = Command {
+    name: "import",
+    description: "Replaces the equation with one from a JSON file",
+    usage: "<file>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let [file] = args else {
+            return Err(Error::command("Expected a file"));
+        };
+
+        let text = std::fs::read_to_string(file).map_err(|error| Error::io(format!("Could not read {file}: {error}")))?;
+        let equation = json::from_json(&text).map_err(|error| Error::format(format!("Invalid JSON expression: {error}")))?;
+
+        state.load(equation);
+        Ok(())
+    }
+};
+
+static SAVE_COMMAND: Command = Command {
+    name: "save",
+    description: "Saves the session to a file",
+    usage: "<file>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let [file] = args else {
+            return Err(Error::command("Expected a file"));
+        };
+
+        std::fs::write(file, session::save(state)).map_err(|error| Error::io(format!("Could not write {file}: {error}")))?;
+        println!("Saved to {file}");
+        Ok(())
+    }
+};
+
+static OPEN_COMMAND: Command = Command {
+    name: "open",
+    description: "Restores a saved session",
+    usage: "<file>",
+
+    execute: |state: &mut State, args: &[&str]| -> Result<(), Error> {
+        let [file] = args else {
+            return Err(Error::command("Expected a file"));
+        };
+
+        let text = std::fs::read_to_string(file).map_err(|error| Error::io(format!("Could not read {file}: {error}")))?;
+        *state = session::open(&text).map_err(|error| Error::format(format!("Could not open session: {error}")))?;
+        Ok(())
+    }
+};
+
+pub static EXIT_COMMAND: Command = Command {
+    name: "q",
+    description: "Exits the program",
+    usage: "",
+
+    // Leaving is up to the caller, run_command returns Flow::Exit for it
+    execute: |_state: &mut State, _args: &[&str]| -> Result<(), Error> {
+        Ok(())
+    }
+};
+
+/// Whether the caller keeps reading commands after run_command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Exit,
+}
+
+/// All commands in the order of the help page
+pub static COMMANDS: [&Command; 26] = [
+    &HELP_COMMAND,
+    &HISTORY_COMMAND,
+    &CHILDREN_COMMAND,
+    &TYPE_COMMAND,
+    &TREE_COMMAND,
+    &RULES_COMMAND,
+    &CHECK_COMMAND,
+    &DIFF_COMMAND,
+    &INTEGRATE_COMMAND,
+    &SUM_COMMAND,
+    &PRODUCT_COMMAND,
+    &CALL_COMMAND,
+    &SIMPLIFY_COMMAND,
+    &NUMERIC_COMMAND,
+    &LOAD_COMMAND,
+    &LET_COMMAND,
+    &DEFS_COMMAND,
+    &DISPLAY_COMMAND,
+    &PRECISION_COMMAND,
+    &RENDER_COMMAND,
+    &LATEX_COMMAND,
+    &EXPORT_COMMAND,
+    &IMPORT_COMMAND,
+    &SAVE_COMMAND,
+    &OPEN_COMMAND,
+    &EXIT_COMMAND,
+];
+
+/// Runs a single input line, an empty line does nothing
+pub fn run_command(state: &mut State, line: &str) -> Result<Flow, Error> {
+    let mut input = line.split_whitespace();
+
+    let Some(command_name) = input.next() else {
+        return Ok(Flow::Continue);
+    };
+    let args: Vec<&str> = input.collect();
+
+    let command = COMMANDS.iter()
+        .find(|command| command.name == command_name)
+        .ok_or_else(|| Error::command(format!("Unknown command: {command_name}")))?;
+
+    (command.execute)(state, &args)?;
+
+    if std::ptr::eq(*command, &EXIT_COMMAND) { Ok(Flow::Exit) } else { Ok(Flow::Continue) }
+}
+
+/// Runs every line without echoing the equation, stops at the first failing command
+/// or at the exit command. Lines starting with # are comments
+pub fn run_script<'a>(state: &mut State, lines: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    for (number, line) in lines.enumerate() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if run_command(state, line).map_err(|error| error.on_line(number + 1))? == Flow::Exit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, ErrorKind};
+    use super::*;
+
+    fn state() -> State {
+        State::new(parse("x ** 2").unwrap())
+    }
+
+    #[test]
+    fn commands_report_structured_errors() {
+        let mut state = state();
+
+        assert_eq!(run_command(&mut state, "frobnicate").unwrap_err(), Error::command("Unknown command: frobnicate"));
+        assert_eq!(run_command(&mut state, "children 3").unwrap_err(), Error::command("Index: 3 out of range: 2"));
+        assert!(matches!(run_command(&mut state, "load 1 +").unwrap_err().kind, ErrorKind::Parse(_)));
+        assert!(matches!(run_command(&mut state, "open /nonexistent/session.json").unwrap_err().kind, ErrorKind::Io(_)));
+    }
+
+    #[test]
+    fn evaluation_errors_keep_their_kind() {
+        let mut state = State::new(parse("0 ** (0 - 1)").unwrap());
+
+        assert_eq!(run_command(&mut state, "numeric").unwrap_err().kind, ErrorKind::DivisionByZero);
+    }
+
+    #[test]
+    fn scripts_stop_at_the_failing_line() {
+        let mut state = state();
+        let error = run_script(&mut state, ["# comment", "diff x", "children 7", "load y"].into_iter()).unwrap_err();
+
+        assert_eq!(error, Error::command("Index: 7 out of range: 1").on_line(3));
+        assert_eq!(error.to_string(), "Line 3: Index: 7 out of range: 1");
+        assert_eq!(state.current.to_string(), "d/dx x ** 2");
+    }
+
+    #[test]
+    fn exit_is_left_to_the_caller() {
+        let mut state = state();
+
+        assert_eq!(run_command(&mut state, "q").unwrap(), Flow::Exit);
+        assert_eq!(run_command(&mut state, "").unwrap(), Flow::Continue);
+
+        run_script(&mut state, ["diff x", "q", "load y"].into_iter()).unwrap();
+        assert_eq!(state.current.to_string(), "d/dx x ** 2");
+    }
+
+    #[test]
+    fn only_dot_exports_the_history() {
+        let mut state = state();
+
+        for format in ["latex", "json"] {
+            let error = run_command(&mut state, &format!("export {format} /nonexistent/out history")).unwrap_err();
+            assert_eq!(error, Error::command("Only dot exports take the history option"));
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Named constants like `pi` and `e`, which stay symbolic until numeric evaluation.
+
+use crate::Real;
+
+pub struct Constant {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub latex: &'static str,
+
+    // Value in the current precision
+    pub value: fn() -> Real,
+}
+
+static PI: Constant = Constant {
+    name: "pi",
+    description: "Ratio of a circle's circumference to its diameter",
+    latex: "\\pi",
+    value: Real::pi,
+};
+
+static E: Constant = Constant {
+    name: "e",
+    description: "Euler's number, base of the natural logarithm",
+    latex: "\\mathrm{e}",
+    value: Real::e,
+};
+
+pub static CONSTANTS: [&Constant; 2] = [
+    &PI,
+    &E,
+];
+
+pub fn find_constant(name: &str) -> Option<&'static Constant> {
+    CONSTANTS.iter().find(|constant| constant.name == name).copied()
+}
@@ -0,0 +1,181 @@
+//! User definitions of constants and functions.
+
+use std::fmt::Display;
+
+use crate::{constants::find_constant, functions::find_function, operations::FunctionCall, parser, Error, Expressions, WrappedReal};
+
+/// User definition of a constant `k = 3` or a function `f(x) = x ** 2 + 1`
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub body: Expressions,
+}
+
+impl Definition {
+    pub fn parse(input: &str) -> Result<Definition, Error> {
+        let (head, body) = input.split_once('=').ok_or(Error::parse("Expected <name> = <expression>"))?;
+        let head = parser::parse(head)?;
+        let body = parser::parse(body)?;
+
+        let (name, parameters) = if let Some(name) = head.get_variable() {
+            (name, vec![])
+        } else if let Some(call) = head.is_function_call() {
+            let parameters = call.args.iter()
+                .map(|arg| arg.get_variable().ok_or_else(|| Error::parse(format!("Invalid parameter: {arg}"))))
+                .collect::<Result<Vec<String>, Error>>()?;
+
+            (call.name, parameters)
+        } else {
+            return Err(Error::parse(format!("Expected a name or function call, got {head}")));
+        };
+
+        Definition::new(name, parameters, body)
+    }
+
+    /// Definition of the name, rejecting duplicate parameters and builtin names
+    pub fn new(name: String, parameters: Vec<String>, body: Expressions) -> Result<Definition, Error> {
+        if let Some(duplicate) = parameters.iter().enumerate().find(|(i, parameter)| parameters[..*i].contains(parameter)) {
+            return Err(Error::parse(format!("Duplicate parameter: {}", duplicate.1)));
+        }
+
+        if find_function(&name).is_some() {
+            return Err(Error::parse(format!("Cannot redefine builtin function: {name}")));
+        }
+
+        if let Some(constant) = std::iter::once(&name).chain(parameters.iter()).find_map(|name| find_constant(name)) {
+            return Err(Error::parse(format!("Cannot redefine builtin constant: {}", constant.name)));
+        }
+
+        Ok(Definition { name, parameters, body })
+    }
+
+    /// Inlines the definition: k = 3, f(a + b) = (a + b) ** 2 + 1
+    pub fn unfold(&self, expression: &Expressions) -> Option<Expressions> {
+        if self.parameters.is_empty() {
+            return if expression.get_variable()? == self.name { Some(self.body.clone()) } else { None };
+        }
+
+        let call = expression.is_function_call()?;
+        if call.name != self.name || call.args.len() != self.parameters.len() {
+            return None;
+        }
+
+        let substitutions: Vec<(String, Expressions)> = self.parameters.iter().cloned().zip(call.args).collect();
+        Some(self.body.substitute_all(&substitutions))
+    }
+
+    /// Replaces an instance of the body with the definition: (a + b) ** 2 + 1 = f(a + b)
+    pub fn fold(&self, expression: &Expressions) -> Option<Expressions> {
+        let mut bindings = vec![];
+        self.bind(&self.body, expression, &mut bindings);
+
+        if bindings.len() != self.parameters.len() || &self.body.substitute_all(&bindings) != expression {
+            return None;
+        }
+
+        if self.parameters.is_empty() {
+            return Some(WrappedReal::new_variable(&self.name).into());
+        }
+
+        let args = self.parameters.iter()
+            .map(|parameter| bindings.iter().find(|(name, _)| name == parameter).map(|(_, value)| value.clone()))
+            .collect::<Option<Vec<Expressions>>>()?;
+
+        Some(FunctionCall::new(&self.name, args).into())
+    }
+
+    // Collects the subexpressions matching each parameter in the pattern
+    fn bind(&self, pattern: &Expressions, expression: &Expressions, bindings: &mut Vec<(String, Expressions)>) {
+        if let Some(name) = pattern.get_variable().filter(|name| self.parameters.contains(name)) {
+            if !bindings.iter().any(|(bound, _)| bound == &name) {
+                bindings.push((name, expression.clone()));
+            }
+            return;
+        }
+
+        let patterns = pattern.get_children();
+        let expressions = expression.get_children();
+
+        if patterns.len() == expressions.len() {
+            for (pattern, expression) in patterns.iter().zip(expressions.iter()) {
+                self.bind(pattern, expression, bindings);
+            }
+        }
+    }
+}
+
+impl Display for Definition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.parameters.is_empty() {
+            write!(f, "{} = {}", self.name, self.body)
+        } else {
+            write!(f, "{}({}) = {}", self.name, self.parameters.join(", "), self.body)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    fn error(input: &str) -> String {
+        Definition::parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_constants_and_functions() {
+        assert_eq!(Definition::parse("k = 3").unwrap().to_string(), "k = 3");
+        assert_eq!(Definition::parse("f(x, y) = x * y").unwrap().to_string(), "f(x, y) = x * y");
+    }
+
+    #[test]
+    fn rejects_invalid_heads() {
+        assert_eq!(error("3 = x"), "Expected a name or function call, got 3");
+        assert_eq!(error("f(x, x) = x"), "Duplicate parameter: x");
+        assert_eq!(error("f(2) = x"), "Invalid parameter: 2");
+        assert_eq!(error("sqrt(x) = x"), "Cannot redefine builtin function: sqrt");
+        assert_eq!(error("f(x)"), "Expected <name> = <expression>");
+    }
+
+    #[test]
+    fn rejects_builtin_constants() {
+        assert_eq!(error("pi = 3"), "Cannot redefine builtin constant: pi");
+        assert_eq!(error("e = 2.7"), "Cannot redefine builtin constant: e");
+        assert_eq!(error("f(e) = e + 1"), "Cannot redefine builtin constant: e");
+    }
+
+    #[test]
+    fn unfold_substitutes_arguments() {
+        let definition = Definition::parse("f(x) = x ** 2 + 1").unwrap();
+
+        assert_eq!(definition.unfold(&parse("f(a + b)").unwrap()).unwrap().to_string(), "(a + b) ** 2 + 1");
+        assert_eq!(definition.unfold(&parse("g(a)").unwrap()), None);
+        assert_eq!(definition.unfold(&parse("f(a, b)").unwrap()), None);
+    }
+
+    #[test]
+    fn fold_finds_the_arguments() {
+        let definition = Definition::parse("f(x) = x ** 2 + 1").unwrap();
+
+        assert_eq!(definition.fold(&parse("(a + b) ** 2 + 1").unwrap()).unwrap().to_string(), "f(a + b)");
+        assert_eq!(definition.fold(&parse("a ** 3 + 1").unwrap()), None);
+    }
+
+    #[test]
+    fn fold_requires_consistent_arguments() {
+        let definition = Definition::parse("f(x) = x * x").unwrap();
+
+        assert_eq!(definition.fold(&parse("a * a").unwrap()).unwrap().to_string(), "f(a)");
+        assert_eq!(definition.fold(&parse("a * b").unwrap()), None);
+    }
+
+    #[test]
+    fn constants_fold_and_unfold() {
+        let definition = Definition::parse("k = 3 * y").unwrap();
+
+        assert_eq!(definition.unfold(&parse("k").unwrap()).unwrap().to_string(), "3 * y");
+        assert_eq!(definition.fold(&parse("3 * y").unwrap()).unwrap().to_string(), "k");
+    }
+}
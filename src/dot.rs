@@ -0,0 +1,113 @@
+//! Graphviz output of expressions and derivations.
+
+use std::{collections::HashMap, fmt::Write, sync::Arc};
+
+use crate::{operations::Operation, Expressions, InnerExpressions, Step};
+
+fn label(expression: &Expressions) -> String {
+    match expression.to_inner() {
+        InnerExpressions::Type(types) => types.to_string(),
+        InnerExpressions::Operation(operation) => match operation {
+            Operation::Addition(_) => "+".to_string(),
+            Operation::Subtraction(_) => "-".to_string(),
+            Operation::Multiplication(_) => "*".to_string(),
+            Operation::Division(_) => "/".to_string(),
+            Operation::Exponentiation(_) => "**".to_string(),
+            Operation::Derivative(derivative) => format!("d/d{}", derivative.variable),
+            Operation::Integral(integral) => format!("int d{}", integral.variable),
+            Operation::FunctionCall(call) => call.name,
+            Operation::Sum(sum) => format!("sum {}", sum.index),
+            Operation::Product(product) => format!("prod {}", product.index),
+        },
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Assigns one node per shared Arc, so subtrees shared between steps are drawn once
+#[derive(Default)]
+struct DotWriter {
+    ids: HashMap<*const InnerExpressions, usize>,
+    output: String,
+}
+
+impl DotWriter {
+    fn node(&mut self, expression: &Expressions, indent: &str) -> usize {
+        let key = Arc::as_ptr(&expression.0);
+
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        let id = self.ids.len();
+        self.ids.insert(key, id);
+
+        writeln!(self.output, "{indent}n{id} [label=\"{}\"];", escape(&label(expression))).unwrap();
+
+        for (i, child) in expression.get_children().iter().enumerate() {
+            let child_id = self.node(child, indent);
+            writeln!(self.output, "{indent}n{id} -> n{child_id} [label=\"{i}\"];").unwrap();
+        }
+
+        id
+    }
+}
+
+pub fn to_dot(expression: &Expressions) -> String {
+    let mut writer = DotWriter::default();
+    writer.node(expression, "    ");
+
+    format!("digraph expression {{\n    node [shape=box];\n{}}}\n", writer.output)
+}
+
+/// Every equation of the history in its own cluster, connected by the applied rules
+pub fn history_to_dot(history: &[Step]) -> String {
+    let mut writer = DotWriter::default();
+    let mut roots = vec![];
+
+    for (i, step) in history.iter().enumerate() {
+        writeln!(writer.output, "    subgraph cluster_{i} {{").unwrap();
+        writeln!(writer.output, "        label=\"Step {i}\";").unwrap();
+        roots.push(writer.node(&step.equation, "        "));
+        writeln!(writer.output, "    }}").unwrap();
+    }
+
+    for (i, step) in history.iter().enumerate().skip(1) {
+        let rule = step.rule.as_deref().unwrap_or("");
+
+        writeln!(
+            writer.output,
+            "    n{} -> n{} [label=\"{}\", style=dashed, ltail=cluster_{}, lhead=cluster_{i}];",
+            roots[i - 1], roots[i], escape(rule), i - 1,
+        ).unwrap();
+    }
+
+    format!("digraph history {{\n    compound=true;\n    node [shape=box];\n{}}}\n", writer.output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, state::State, Multiplication};
+    use super::*;
+
+    #[test]
+    fn nodes_and_edges() {
+        assert_eq!(
+            to_dot(&parse("a + 1").unwrap()),
+            "digraph expression {\n    node [shape=box];\n    n0 [label=\"+\"];\n    n1 [label=\"a\"];\n    n0 -> n1 [label=\"0\"];\n    n2 [label=\"1\"];\n    n0 -> n2 [label=\"1\"];\n}\n",
+        );
+    }
+
+    #[test]
+    fn shared_subtrees_are_drawn_once() {
+        let mut state = State::new(parse("(a + b) * c").unwrap());
+        let shared = state.current.get_children()[0].clone();
+        state.apply(Multiplication::new(shared.clone(), shared).into(), "Square");
+
+        let dot = history_to_dot(&state.history);
+        assert_eq!(dot.matches("label=\"+\"").count(), 1);
+        assert!(dot.contains("label=\"Square\""));
+    }
+}
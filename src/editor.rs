@@ -0,0 +1,170 @@
+use std::path::PathBuf;
+
+use rustyline::{
+    completion::{Completer, Pair},
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::Validator,
+    Context, Helper,
+};
+
+use ruth::{commands::COMMANDS, functions::FUNCTIONS, rules, state::State};
+
+// Completion candidates of the REPL, refreshed from the state before every line
+#[derive(Default)]
+pub struct ReplHelper {
+    // Index and name of every rule applicable to the selection
+    rules: Vec<(usize, String)>,
+
+    // Variables of the current equation
+    variables: Vec<String>,
+}
+
+impl ReplHelper {
+    pub fn update(&mut self, state: &State) {
+        self.rules = rules::find_all_rules(&state.selection(), &state.definitions).iter()
+            .enumerate()
+            .map(|(i, rule)| (i, rule.name.to_string()))
+            .collect();
+
+        self.variables = state.current.variables();
+    }
+}
+
+// Fixed values of an argument, the position counts the arguments before it
+fn fixed_values(command: &str, position: usize, previous: Option<&str>) -> &'static [&'static str] {
+    match (command, position) {
+        ("children", 0) => &["top"],
+        ("display", 0) => &["minimal", "verbose"],
+        ("render", 0) => &["inline", "2d"],
+        ("export", 0) => &["latex", "dot", "json"],
+        ("export", 2) if previous == Some("dot") => &["history"],
+        _ => &[],
+    }
+}
+
+fn candidates<'a>(prefix: &str, options: impl Iterator<Item = (String, String)> + 'a) -> Vec<Pair> {
+    options
+        .filter(|(_, replacement)| replacement.starts_with(prefix))
+        .map(|(display, replacement)| Pair { display, replacement })
+        .collect()
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = &line[start..];
+
+        // The first word is a command, the rest depends on the command
+        let Some(command) = line[..start].split_whitespace().next() else {
+            let commands = COMMANDS.iter().map(|command| (command.name.to_string(), format!("{} ", command.name)));
+            return Ok((start, candidates(word, commands)));
+        };
+
+        let arguments: Vec<&str> = line[..start].split_whitespace().skip(1).collect();
+
+        match command {
+            "rules" => {
+                let rules = self.rules.iter().map(|(i, name)| (format!("{i}: {name}"), i.to_string()));
+                Ok((start, candidates(word, rules)))
+            },
+            // Commands that take a variable or an expression of them
+            "diff" | "int" | "sum" | "prod" | "load" | "let" => {
+                let variables = self.variables.iter().map(|name| (name.clone(), name.clone()));
+                Ok((start, candidates(word, variables)))
+            },
+            "call" => {
+                let functions = FUNCTIONS.iter().map(|function| (function.name.to_string(), function.name.to_string()));
+                Ok((start, candidates(word, functions)))
+            },
+            _ => {
+                let values = fixed_values(command, arguments.len(), arguments.first().copied());
+                Ok((start, candidates(word, values.iter().map(|value| (value.to_string(), value.to_string())))))
+            },
+        }
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+// File of the input history in the user's data directory
+pub fn history_path() -> Option<PathBuf> {
+    let directory = dirs::data_dir()?.join("ruth");
+    std::fs::create_dir_all(&directory).ok()?;
+
+    Some(directory.join("history.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rustyline::history::DefaultHistory;
+
+    use ruth::parser::parse;
+    use super::*;
+
+    fn complete(helper: &ReplHelper, line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        let (start, candidates) = helper.complete(line, line.len(), &Context::new(&history)).unwrap();
+
+        (start, candidates.into_iter().map(|candidate| candidate.replacement).collect())
+    }
+
+    fn helper(equation: &str) -> ReplHelper {
+        let mut helper = ReplHelper::default();
+        ReplHelper::update(&mut helper, &State::new(parse(equation).unwrap()));
+        helper
+    }
+
+    #[test]
+    fn completes_command_names() {
+        let helper = helper("x");
+
+        assert_eq!(complete(&helper, "si"), (0, vec!["simplify ".to_string()]));
+        assert_eq!(complete(&helper, "  h"), (2, vec!["help ".to_string(), "history ".to_string()]));
+    }
+
+    #[test]
+    fn completes_rule_indices() {
+        let helper = helper("x * (a + b)");
+        let (start, candidates) = complete(&helper, "rules ");
+
+        assert_eq!(start, 6);
+        assert_eq!(candidates.len(), helper.rules.len());
+        assert_eq!(candidates[0], "0");
+    }
+
+    #[test]
+    fn completes_variables_of_the_equation() {
+        let helper = helper("alpha + beta * alpha2");
+
+        assert_eq!(complete(&helper, "diff al"), (5, vec!["alpha".to_string(), "alpha2".to_string()]));
+        assert_eq!(complete(&helper, "diff z"), (5, vec![]));
+    }
+
+    #[test]
+    fn completes_fixed_arguments_of_other_commands() {
+        let helper = helper("alpha + beta");
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(complete(&helper, "display "), (8, strings(&["minimal", "verbose"])));
+        assert_eq!(complete(&helper, "render 2"), (7, strings(&["2d"])));
+        assert_eq!(complete(&helper, "export dot out.dot h"), (19, strings(&["history"])));
+        assert_eq!(complete(&helper, "export json out.json "), (21, vec![]));
+        assert_eq!(complete(&helper, "call sq"), (5, strings(&["sqrt"])));
+
+        for line in ["precision ", "save ", "children ", "export "] {
+            assert!(complete(&helper, line).1.iter().all(|candidate| candidate != "alpha"), "{line}");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Errors of parsing and evaluating expressions.
+
+use std::fmt::Display;
+
+/// What went wrong
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// An operation got operands it is not defined for, like a function with the wrong number of arguments
+    TypeMismatch(String),
+    /// The result does not fit into its type
+    Overflow(String),
+    DivisionByZero,
+    /// A function evaluated outside of its domain, like `sqrt(-1)`
+    Domain(String),
+    /// A value was required but the expression still contains the variable
+    UnboundVariable(String),
+    Parse(String),
+    /// A command got missing or invalid arguments
+    Command(String),
+    /// Reading or writing a file failed
+    Io(String),
+    /// A file is not a valid document of its format or version
+    Format(String),
+}
+
+/// Error with the location of the failing subexpression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+
+    /// Child indices from the evaluated expression down to the failing subexpression
+    pub path: Vec<usize>,
+
+    /// Line of the script the failing command is on
+    pub line: Option<usize>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self { kind, path: vec![], line: None }
+    }
+
+    pub fn type_mismatch(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::TypeMismatch(message.into()))
+    }
+
+    pub fn overflow(operation: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Overflow(operation.into()))
+    }
+
+    pub fn division_by_zero() -> Self {
+        Self::new(ErrorKind::DivisionByZero)
+    }
+
+    pub fn domain(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Domain(message.into()))
+    }
+
+    pub fn unbound_variable(name: impl Into<String>) -> Self {
+        Self::new(ErrorKind::UnboundVariable(name.into()))
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse(message.into()))
+    }
+
+    pub fn command(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Command(message.into()))
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Io(message.into()))
+    }
+
+    pub fn format(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Format(message.into()))
+    }
+
+    /// Same error seen from the parent, where the failing expression is the child with the index
+    pub fn in_child(mut self, index: usize) -> Self {
+        self.path.insert(0, index);
+        self
+    }
+
+    /// Same error raised by the command on the line of a script
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::TypeMismatch(message) => write!(f, "Type mismatch: {message}"),
+            ErrorKind::Overflow(operation) => write!(f, "Overflow in {operation}"),
+            ErrorKind::DivisionByZero => write!(f, "Division by zero"),
+            ErrorKind::Domain(message) => write!(f, "Domain error: {message}"),
+            ErrorKind::UnboundVariable(name) => write!(f, "Unbound variable: {name}"),
+            ErrorKind::Parse(message) | ErrorKind::Command(message) | ErrorKind::Io(message) | ErrorKind::Format(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "Line {line}: ")?;
+        }
+
+        write!(f, "{}", self.kind)?;
+
+        if !self.path.is_empty() {
+            let path: Vec<String> = self.path.iter().map(usize::to_string).collect();
+            write!(f, " (at child {})", path.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
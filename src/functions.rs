@@ -0,0 +1,191 @@
+//! Builtin functions like `sqrt`, `sin` and `ln`.
+
+use crate::{operations::{Addition, BinaryOperation, Exponentiation, FunctionCall, Multiplication, Sub}, Complex, Error, Expressions, Rational, Real, Types, Wrapper, WrappedComplex, WrappedNatural, WrappedRational, WrappedZahl};
+
+pub struct Function {
+    pub name: &'static str,
+    pub description: &'static str,
+
+    // Numeric evaluation of a real argument
+    pub evaluate: fn(Real) -> Result<Real, Error>,
+
+    // Exact value for special constant arguments, e.g. sqrt(4) = 2
+    pub exact: fn(&Types) -> Option<Types>,
+
+    // Derivative at the argument, used by the chain rule
+    pub derivative: fn(&Expressions) -> Expressions,
+}
+
+fn integer_sqrt(value: i64) -> Option<i64> {
+    if value < 0 {
+        return None;
+    }
+
+    let root = (value as f64).sqrt().round() as i64;
+    if root * root == value { Some(root) } else { None }
+}
+
+fn call(name: &str, argument: &Expressions) -> Expressions {
+    FunctionCall::new(name, vec![argument.clone()]).into()
+}
+
+static SQRT: Function = Function {
+    name: "sqrt",
+    description: "Square root",
+
+    evaluate: Real::sqrt,
+    exact: |value: &Types| {
+        // Roots of negative numbers are imaginary, sqrt(-x) = sqrt(x) * i
+        if value.is_negative() {
+            let root = match value {
+                Types::Zahl(Wrapper::Constant(zahl)) => Complex::Exact(Rational::new(0, 1), Rational::new(integer_sqrt(-(zahl.get() as i64))?, 1)),
+                Types::Rational(Wrapper::Constant(rational)) => Complex::Exact(
+                    Rational::new(0, 1),
+                    Rational::new(integer_sqrt(-rational.numerator())?, integer_sqrt(rational.denominator())?),
+                ),
+                Types::Real(Wrapper::Constant(real)) => Complex::Approximate(Real::fraction(0, 1), Real::fraction(0, 1).sub(real.clone()).ok()?.sqrt().ok()?),
+                _ => return None,
+            };
+
+            return Some(WrappedComplex::new(root).into());
+        }
+
+        match value {
+            Types::Natural(Wrapper::Constant(natural)) => Some(WrappedNatural::new(integer_sqrt(natural.get() as i64)? as u32).into()),
+            Types::Zahl(Wrapper::Constant(zahl)) => Some(WrappedNatural::new(integer_sqrt(zahl.get() as i64)? as u32).into()),
+            Types::Rational(Wrapper::Constant(rational)) => Some(WrappedRational::new(
+                integer_sqrt(rational.numerator())?,
+                integer_sqrt(rational.denominator())?,
+            ).into()),
+            _ => None,
+        }
+    },
+    // 1/2 * sqrt(u) ** -1
+    derivative: |argument: &Expressions| {
+        Multiplication::new(
+            WrappedRational::new(1, 2).into(),
+            Exponentiation::new(call("sqrt", argument), WrappedZahl::new(-1).into()).into(),
+        ).into()
+    },
+};
+
+static LN: Function = Function {
+    name: "ln",
+    description: "Natural logarithm",
+
+    evaluate: Real::ln,
+    exact: |value: &Types| {
+        if value.is_one() { Some(WrappedNatural::new(0).into()) } else { None }
+    },
+    // u ** -1
+    derivative: |argument: &Expressions| {
+        Exponentiation::new(argument.clone(), WrappedZahl::new(-1).into()).into()
+    },
+};
+
+static EXP: Function = Function {
+    name: "exp",
+    description: "Exponential function",
+
+    evaluate: Real::exp,
+    exact: |value: &Types| {
+        if value.is_zero() { Some(WrappedNatural::new(1).into()) } else { None }
+    },
+    derivative: |argument: &Expressions| call("exp", argument),
+};
+
+static SIN: Function = Function {
+    name: "sin",
+    description: "Sine",
+
+    evaluate: Real::sin,
+    exact: |value: &Types| {
+        if value.is_zero() { Some(WrappedNatural::new(0).into()) } else { None }
+    },
+    derivative: |argument: &Expressions| call("cos", argument),
+};
+
+static COS: Function = Function {
+    name: "cos",
+    description: "Cosine",
+
+    evaluate: Real::cos,
+    exact: |value: &Types| {
+        if value.is_zero() { Some(WrappedNatural::new(1).into()) } else { None }
+    },
+    // -1 * sin(u)
+    derivative: |argument: &Expressions| {
+        Multiplication::new(WrappedZahl::new(-1).into(), call("sin", argument)).into()
+    },
+};
+
+static TAN: Function = Function {
+    name: "tan",
+    description: "Tangent",
+
+    evaluate: Real::tan,
+    exact: |value: &Types| {
+        if value.is_zero() { Some(WrappedNatural::new(0).into()) } else { None }
+    },
+    // 1 + tan(u) ** 2
+    derivative: |argument: &Expressions| {
+        Addition::new(
+            WrappedNatural::new(1).into(),
+            Exponentiation::new(call("tan", argument), WrappedNatural::new(2).into()).into(),
+        ).into()
+    },
+};
+
+pub static FUNCTIONS: [&Function; 6] = [
+    &SQRT,
+    &LN,
+    &EXP,
+    &SIN,
+    &COS,
+    &TAN,
+];
+
+pub fn find_function(name: &str) -> Option<&'static Function> {
+    FUNCTIONS.iter().find(|function| function.name == name).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    fn evaluated(input: &str) -> String {
+        parse(input).unwrap().solve().unwrap().to_string()
+    }
+
+    #[test]
+    fn exact_values() {
+        assert_eq!(evaluated("sqrt(16)"), "4");
+        assert_eq!(evaluated("sqrt(9/4)"), "3/2");
+        assert_eq!(evaluated("ln(1)"), "0");
+        assert_eq!(evaluated("exp(0)"), "1");
+        assert_eq!(evaluated("sin(0) + cos(0)"), "1");
+    }
+
+    #[test]
+    fn inexact_arguments_stay_symbolic() {
+        assert_eq!(evaluated("sqrt(2)"), "sqrt(2)");
+        assert_eq!(evaluated("ln(2)"), "ln(2)");
+    }
+
+    #[test]
+    fn real_arguments_are_evaluated() {
+        assert_eq!(evaluated("sqrt(2.25)"), "1.5");
+        assert_eq!(evaluated("exp(0.0)"), "1");
+    }
+
+    #[test]
+    fn domain_errors() {
+        assert!(parse("ln(0.0)").unwrap().solve().is_err());
+        assert!(parse("ln(0.0 - 1)").unwrap().solve().is_err());
+    }
+
+    #[test]
+    fn roots_of_negative_numbers_are_imaginary() {
+        assert_eq!(evaluated("sqrt(0 - 4)"), "2 * i");
+    }
+}
@@ -0,0 +1,321 @@
+//! Versioned JSON schema of expressions.
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    operations::Operation, Addition, Complex, BinaryOperation, Derivative, Division, Error, Exponentiation, Expressions, FunctionCall, InnerExpressions,
+    Integral, Multiplication, Natural, Product, Rational, Real, Subtraction, Sum, Types, Wrapper, Zahl,
+};
+
+/// Version of the schema below, bumped on every incompatible change
+pub const SCHEMA_VERSION: u64 = 1;
+
+// Shared part of every wrapped type: constants carry their value,
+// variables their name and nested expressions the expression
+fn wrapper_to_value<T>(wrapper: &Wrapper<T>, constant: impl Fn(&T) -> Map<String, Value>) -> Map<String, Value> {
+    match wrapper {
+        Wrapper::Constant(value) => {
+            let mut object = constant(value);
+            object.insert("kind".to_string(), json!("constant"));
+            object
+        }
+        Wrapper::Variable(name) => Map::from_iter([
+            ("kind".to_string(), json!("variable")),
+            ("name".to_string(), json!(name)),
+        ]),
+        Wrapper::Expression(expression) => Map::from_iter([
+            ("kind".to_string(), json!("expression")),
+            ("expression".to_string(), to_value(expression)),
+        ]),
+    }
+}
+
+fn type_to_value(types: &Types) -> Value {
+    let mut object = match types {
+        Types::Natural(natural) => wrapper_to_value(natural, |value| Map::from_iter([("value".to_string(), json!(value.get()))])),
+        Types::Zahl(zahl) => wrapper_to_value(zahl, |value| Map::from_iter([("value".to_string(), json!(value.get()))])),
+        Types::Rational(rational) => wrapper_to_value(rational, |value| Map::from_iter([
+            ("numerator".to_string(), json!(value.numerator())),
+            ("denominator".to_string(), json!(value.denominator())),
+        ])),
+        // Decimals are strings, so none of their digits are lost
+        Types::Real(real) => wrapper_to_value(real, |value| match value {
+            Real::Double(double) => Map::from_iter([("value".to_string(), json!(double))]),
+            Real::Decimal(decimal) => Map::from_iter([("value".to_string(), json!(decimal.to_string()))]),
+        }),
+        // Exact parts are fractions, approximate parts plain numbers
+        Types::Complex(complex) => wrapper_to_value(complex, |value| match value {
+            Complex::Exact(real, imaginary) => Map::from_iter([
+                ("real".to_string(), json!({ "numerator": real.numerator(), "denominator": real.denominator() })),
+                ("imaginary".to_string(), json!({ "numerator": imaginary.numerator(), "denominator": imaginary.denominator() })),
+            ]),
+            Complex::Approximate(real, imaginary) => Map::from_iter([
+                ("real".to_string(), json!(real.get())),
+                ("imaginary".to_string(), json!(imaginary.get())),
+            ]),
+        }),
+    };
+
+    object.insert("type".to_string(), json!(types.get_type()));
+    Value::Object(object)
+}
+
+fn operation_to_value(operation: &Operation) -> Value {
+    match operation {
+        Operation::Addition(addition) => binary_to_value("Addition", &addition.left, &addition.right),
+        Operation::Subtraction(subtraction) => binary_to_value("Subtraction", &subtraction.left, &subtraction.right),
+        Operation::Multiplication(multiplication) => binary_to_value("Multiplication", &multiplication.left, &multiplication.right),
+        Operation::Division(division) => binary_to_value("Division", &division.left, &division.right),
+        Operation::Exponentiation(exponentiation) => binary_to_value("Exponentiation", &exponentiation.left, &exponentiation.right),
+        Operation::Derivative(derivative) => json!({
+            "kind": "operation",
+            "operation": "Derivative",
+            "variable": derivative.variable,
+            "expression": to_value(&derivative.expression),
+        }),
+        Operation::Integral(integral) => {
+            let mut value = json!({
+                "kind": "operation",
+                "operation": "Integral",
+                "variable": integral.variable,
+                "expression": to_value(&integral.expression),
+            });
+
+            if let Some((lower, upper)) = &integral.bounds {
+                value["lower"] = to_value(lower);
+                value["upper"] = to_value(upper);
+            }
+
+            value
+        }
+        Operation::FunctionCall(call) => json!({
+            "kind": "operation",
+            "operation": "FunctionCall",
+            "name": call.name,
+            "args": call.args.iter().map(to_value).collect::<Vec<_>>(),
+        }),
+        Operation::Sum(sum) => series_to_value("Sum", &sum.index, &sum.from, &sum.to, &sum.body),
+        Operation::Product(product) => series_to_value("Product", &product.index, &product.from, &product.to, &product.body),
+    }
+}
+
+fn binary_to_value(operation: &str, left: &Expressions, right: &Expressions) -> Value {
+    json!({
+        "kind": "operation",
+        "operation": operation,
+        "left": to_value(left),
+        "right": to_value(right),
+    })
+}
+
+fn series_to_value(operation: &str, index: &str, from: &Expressions, to: &Expressions, body: &Expressions) -> Value {
+    json!({
+        "kind": "operation",
+        "operation": operation,
+        "index": index,
+        "from": to_value(from),
+        "to": to_value(to),
+        "body": to_value(body),
+    })
+}
+
+pub fn to_value(expression: &Expressions) -> Value {
+    match expression.to_inner() {
+        InnerExpressions::Type(types) => type_to_value(&types),
+        InnerExpressions::Operation(operation) => operation_to_value(&operation),
+    }
+}
+
+/// The whole document, tagged with the schema version
+pub fn to_json(expression: &Expressions) -> String {
+    let document = json!({
+        "version": SCHEMA_VERSION,
+        "expression": to_value(expression),
+    });
+
+    serde_json::to_string_pretty(&document).unwrap()
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, Error> {
+    value.get(name).ok_or_else(|| Error::format(format!("Missing field \"{name}\"")))
+}
+
+fn string_field<'a>(value: &'a Value, name: &str) -> Result<&'a str, Error> {
+    field(value, name)?.as_str().ok_or_else(|| Error::format(format!("Field \"{name}\" is not a string")))
+}
+
+fn integer_field(value: &Value, name: &str) -> Result<i64, Error> {
+    field(value, name)?.as_i64().ok_or_else(|| Error::format(format!("Field \"{name}\" is not an integer")))
+}
+
+fn expression_field(value: &Value, name: &str) -> Result<Expressions, Error> {
+    from_value(field(value, name)?)
+}
+
+fn real_field(value: &Value, name: &str) -> Result<f64, Error> {
+    field(value, name)?.as_f64().ok_or_else(|| Error::format(format!("Field \"{name}\" is not a number")))
+}
+
+// Fraction from the numerator and denominator fields of the object
+fn rational(value: &Value) -> Result<Rational, Error> {
+    let (numerator, denominator) = (integer_field(value, "numerator")?, integer_field(value, "denominator")?);
+    if denominator == 0 {
+        return Err(Error::format("Rational with zero denominator"));
+    }
+    Rational::try_new(numerator, denominator).map_err(|error| Error::format(format!("Invalid rational: {error}")))
+}
+
+fn rational_field(value: &Value, name: &str) -> Result<Rational, Error> {
+    rational(field(value, name)?)
+}
+
+fn wrapper_from_value<T>(value: &Value, constant: impl Fn(&Value) -> Result<T, Error>) -> Result<Wrapper<T>, Error> {
+    match string_field(value, "kind")? {
+        "constant" => Ok(Wrapper::Constant(constant(value)?)),
+        "variable" => Ok(Wrapper::Variable(string_field(value, "name")?.to_string())),
+        "expression" => Ok(Wrapper::Expression(expression_field(value, "expression")?)),
+        kind => Err(Error::format(format!("Unknown kind \"{kind}\""))),
+    }
+}
+
+fn type_from_value(value: &Value) -> Result<Types, Error> {
+    let types = match string_field(value, "type")? {
+        "Natural" => Types::Natural(wrapper_from_value(value, |value| {
+            let natural = integer_field(value, "value")?;
+            Ok(Natural::new(u32::try_from(natural).map_err(|_| Error::format(format!("{natural} is not a natural number")))?))
+        })?),
+        "Zahl" => Types::Zahl(wrapper_from_value(value, |value| {
+            let zahl = integer_field(value, "value")?;
+            Ok(Zahl::new(i32::try_from(zahl).map_err(|_| Error::format(format!("{zahl} is out of range")))?))
+        })?),
+        "Rational" => Types::Rational(wrapper_from_value(value, rational)?),
+        "Real" => Types::Real(wrapper_from_value(value, |value| match field(value, "value")?.as_str() {
+            Some(digits) => digits.parse().map(Real::Decimal).map_err(|_| Error::format(format!("{digits} is not a decimal"))),
+            None => Ok(Real::new(real_field(value, "value")?)),
+        })?),
+        "Complex" => Types::Complex(wrapper_from_value(value, |value| {
+            if field(value, "real")?.is_object() {
+                Ok(Complex::Exact(rational_field(value, "real")?, rational_field(value, "imaginary")?))
+            } else {
+                Ok(Complex::approximate(real_field(value, "real")?, real_field(value, "imaginary")?))
+            }
+        })?),
+        name => return Err(Error::format(format!("Unknown type \"{name}\""))),
+    };
+
+    Ok(types)
+}
+
+fn operands(value: &Value) -> Result<(Expressions, Expressions), Error> {
+    Ok((expression_field(value, "left")?, expression_field(value, "right")?))
+}
+
+fn operation_from_value(value: &Value) -> Result<Expressions, Error> {
+    match string_field(value, "operation")? {
+        "Addition" => operands(value).map(|(left, right)| Addition::new(left, right).into()),
+        "Subtraction" => operands(value).map(|(left, right)| Subtraction::new(left, right).into()),
+        "Multiplication" => operands(value).map(|(left, right)| Multiplication::new(left, right).into()),
+        "Division" => operands(value).map(|(left, right)| Division::new(left, right).into()),
+        "Exponentiation" => operands(value).map(|(left, right)| Exponentiation::new(left, right).into()),
+        "Derivative" => Ok(Derivative::new(expression_field(value, "expression")?, string_field(value, "variable")?).into()),
+        "Integral" => {
+            let expression = expression_field(value, "expression")?;
+            let variable = string_field(value, "variable")?;
+
+            match (value.get("lower"), value.get("upper")) {
+                (None, None) => Ok(Integral::new(expression, variable).into()),
+                (Some(lower), Some(upper)) => Ok(Integral::new_definite(expression, variable, from_value(lower)?, from_value(upper)?).into()),
+                _ => Err(Error::format("Integral needs both or neither of \"lower\" and \"upper\"")),
+            }
+        }
+        "FunctionCall" => {
+            let args = field(value, "args")?.as_array().ok_or_else(|| Error::format("Field \"args\" is not an array"))?;
+            let args = args.iter().map(from_value).collect::<Result<Vec<_>, _>>()?;
+
+            Ok(FunctionCall::new(string_field(value, "name")?, args).into())
+        }
+        "Sum" => Ok(Sum::new(
+            string_field(value, "index")?,
+            expression_field(value, "from")?,
+            expression_field(value, "to")?,
+            expression_field(value, "body")?,
+        ).into()),
+        "Product" => Ok(Product::new(
+            string_field(value, "index")?,
+            expression_field(value, "from")?,
+            expression_field(value, "to")?,
+            expression_field(value, "body")?,
+        ).into()),
+        name => Err(Error::format(format!("Unknown operation \"{name}\""))),
+    }
+}
+
+pub fn from_value(value: &Value) -> Result<Expressions, Error> {
+    match string_field(value, "kind")? {
+        "operation" => operation_from_value(value),
+        _ => Ok(type_from_value(value)?.into()),
+    }
+}
+
+pub fn from_json(text: &str) -> Result<Expressions, Error> {
+    let document: Value = serde_json::from_str(text).map_err(|error| Error::format(format!("Invalid JSON: {error}")))?;
+
+    match field(&document, "version")?.as_u64() {
+        Some(SCHEMA_VERSION) => from_value(field(&document, "expression")?),
+        Some(version) => Err(Error::format(format!("Unsupported schema version {version}, expected {SCHEMA_VERSION}"))),
+        None => Err(Error::format("Field \"version\" is not a number")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    fn round_trip(expression: &Expressions) -> Expressions {
+        from_json(&to_json(expression)).unwrap()
+    }
+
+    #[test]
+    fn round_trip_of_types() {
+        for input in ["3", "-3", "-1/2", "0.25", "x", "2 + 3 * i", "1/2 * i"] {
+            let expression = parse(input).unwrap().solve().unwrap().into();
+            assert_eq!(round_trip(&expression), expression, "{input}");
+        }
+    }
+
+    #[test]
+    fn round_trip_of_operations() {
+        let inputs = ["a + b - c * d ** e", "sqrt(x) + f(x, y)", "sum(k, 1, n, k) * prod(j, 1, 3, j)"];
+
+        for input in inputs {
+            let expression = parse(input).unwrap();
+            assert_eq!(round_trip(&expression), expression, "{input}");
+        }
+
+        let integral: Expressions = Integral::new_definite(parse("x").unwrap(), "x", parse("0").unwrap(), parse("1").unwrap()).into();
+        let derivative: Expressions = Derivative::new(integral.clone(), "y").into();
+        assert_eq!(round_trip(&derivative), derivative);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let document = to_json(&parse("1").unwrap()).replace("\"version\": 1", "\"version\": 2");
+        assert_eq!(from_json(&document).unwrap_err(), Error::format("Unsupported schema version 2, expected 1"));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert!(from_json("{").unwrap_err().to_string().starts_with("Invalid JSON"));
+        assert_eq!(from_json("{\"version\": 1}").unwrap_err(), Error::format("Missing field \"expression\""));
+
+        let document = json!({ "version": 1, "expression": { "kind": "constant", "type": "Natural", "value": -1 } });
+        assert_eq!(from_json(&document.to_string()).unwrap_err(), Error::format("-1 is not a natural number"));
+
+        let document = json!({ "version": 1, "expression": { "kind": "constant", "type": "Rational", "numerator": 1, "denominator": 0 } });
+        assert_eq!(from_json(&document.to_string()).unwrap_err(), Error::format("Rational with zero denominator"));
+
+        let document = json!({ "version": 1, "expression": { "kind": "constant", "type": "Rational", "numerator": 1, "denominator": i64::MIN } });
+        assert_eq!(from_json(&document.to_string()).unwrap_err(), Error::format("Invalid rational: Overflow in 1/-9223372036854775808"));
+    }
+}
@@ -0,0 +1,174 @@
+//! LaTeX output of expressions and derivations.
+
+use crate::{constants::find_constant, functions::find_function, operations::{Operation, Precedence, INTEGRATION_CONSTANT}, Complex, Expressions, InnerExpressions, Rational, Step, TypeTrait, Types, Wrapper};
+
+pub fn to_latex(expression: &Expressions) -> String {
+    match expression.to_inner() {
+        InnerExpressions::Type(types) => type_to_latex(&types),
+        InnerExpressions::Operation(operation) => operation_to_latex(&operation),
+    }
+}
+
+// Wraps the operand in parentheses if it binds weaker than required
+fn operand(expression: &Expressions, required: Precedence) -> String {
+    if expression.precedence() < required {
+        format!("\\left({}\\right)", to_latex(expression))
+    } else {
+        to_latex(expression)
+    }
+}
+
+// Body of a binder, which extends to the right anyway
+fn body(expression: &Expressions) -> String {
+    if expression.precedence() == Precedence::Binder {
+        to_latex(expression)
+    } else {
+        operand(expression, Precedence::Product)
+    }
+}
+
+fn rational_to_latex(rational: &Rational) -> String {
+    if rational.denominator() == 1 {
+        return rational.numerator().to_string();
+    }
+
+    let sign = if rational.numerator() < 0 { "-" } else { "" };
+    format!("{sign}\\frac{{{}}}{{{}}}", rational.numerator().abs(), rational.denominator())
+}
+
+// a + bi from the parts, leaving out zero parts and a unit coefficient
+fn complex_to_latex(complex: &Complex) -> String {
+    let (real, imaginary) = match complex {
+        Complex::Exact(real, imaginary) => (rational_to_latex(real), rational_to_latex(imaginary)),
+        Complex::Approximate(real, imaginary) => (real.to_string(), imaginary.to_string()),
+    };
+
+    let (sign, magnitude) = match imaginary.strip_prefix('-') {
+        Some(magnitude) => ("-", magnitude),
+        None => ("+", imaginary.as_str()),
+    };
+    let unit = if magnitude == "1" { "i".to_string() } else { format!("{magnitude}i") };
+
+    match (real.as_str(), magnitude) {
+        (_, "0") => real,
+        ("0" | "-0", _) if sign == "-" => format!("-{unit}"),
+        ("0" | "-0", _) => unit,
+        _ => format!("{real} {sign} {unit}"),
+    }
+}
+
+fn type_to_latex(types: &Types) -> String {
+    match types {
+        Types::Rational(Wrapper::Constant(rational)) => rational_to_latex(rational),
+        Types::Complex(Wrapper::Constant(complex)) => complex_to_latex(complex),
+        _ => match types.get_variable() {
+            Some(INTEGRATION_CONSTANT) => "C".to_string(),
+            name => match name.and_then(find_constant) {
+                Some(constant) => constant.latex.to_string(),
+                None => types.to_string(),
+            },
+        },
+    }
+}
+
+fn operation_to_latex(operation: &Operation) -> String {
+    match operation {
+        Operation::Addition(add) => format!("{} + {}", operand(&add.left, Precedence::Sum), operand(&add.right, Precedence::Product)),
+        Operation::Subtraction(sub) => format!("{} - {}", operand(&sub.left, Precedence::Sum), operand(&sub.right, Precedence::Product)),
+        Operation::Multiplication(mul) => format!("{} \\cdot {}", operand(&mul.left, Precedence::Product), operand(&mul.right, Precedence::Power)),
+        Operation::Division(div) => format!("\\frac{{{}}}{{{}}}", to_latex(&div.left), to_latex(&div.right)),
+        Operation::Exponentiation(exp) => format!("{}^{{{}}}", operand(&exp.left, Precedence::Atom), to_latex(&exp.right)),
+        Operation::Derivative(derivative) => format!("\\frac{{d}}{{d{}}} {}", derivative.variable, body(&derivative.expression)),
+        Operation::Integral(integral) => {
+            let bounds = match &integral.bounds {
+                Some((lower, upper)) => format!("_{{{}}}^{{{}}}", to_latex(lower), to_latex(upper)),
+                None => String::new(),
+            };
+
+            format!("\\int{bounds} {} \\, d{}", body(&integral.expression), integral.variable)
+        },
+        Operation::FunctionCall(call) => {
+            let args = call.args.iter().map(to_latex).collect::<Vec<String>>().join(", ");
+
+            match call.name.as_str() {
+                "sqrt" => format!("\\sqrt{{{args}}}"),
+                name if find_function(name).is_some() => format!("\\{name}\\left({args}\\right)"),
+                name => format!("{name}\\left({args}\\right)"),
+            }
+        },
+        Operation::Sum(sum) => format!("\\sum_{{{} = {}}}^{{{}}} {}", sum.index, to_latex(&sum.from), to_latex(&sum.to), body(&sum.body)),
+        Operation::Product(product) => format!("\\prod_{{{} = {}}}^{{{}}} {}", product.index, to_latex(&product.from), to_latex(&product.to), body(&product.body)),
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\textbackslash ").replace('_', "\\_").replace('&', "\\&").replace('%', "\\%").replace('#', "\\#")
+}
+
+/// Whole derivation as align* block, each step annotated with its rule
+pub fn history_to_latex(history: &[Step]) -> String {
+    let lines: Vec<String> = history.iter().map(|step| {
+        match &step.rule {
+            Some(rule) => format!("  &= {} && \\text{{{}}}", to_latex(&step.equation), escape_text(rule)),
+            None => format!("  & {}", to_latex(&step.equation)),
+        }
+    }).collect();
+
+    format!("\\begin{{align*}}\n{}\n\\end{{align*}}\n", lines.join(" \\\\\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, state::State, Derivative, Integral};
+    use super::*;
+
+    fn latex(input: &str) -> String {
+        to_latex(&parse(input).unwrap())
+    }
+
+    #[test]
+    fn operations() {
+        assert_eq!(latex("a * (b + c)"), "a \\cdot \\left(b + c\\right)");
+        assert_eq!(latex("x ** (n + 1)"), "x^{n + 1}");
+        assert_eq!(latex("-1/2"), "-\\frac{1}{2}");
+    }
+
+    #[test]
+    fn functions_and_binders() {
+        assert_eq!(latex("sqrt(x) + sin(x) + f(x)"), "\\sqrt{x} + \\sin\\left(x\\right) + f\\left(x\\right)");
+        assert_eq!(latex("sum(k, 1, n, k)"), "\\sum_{k = 1}^{n} k");
+        assert_eq!(to_latex(&Derivative::new(parse("x ** 2").unwrap(), "x").into()), "\\frac{d}{dx} x^{2}");
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(latex("2 * pi"), "2 \\cdot \\pi");
+        assert_eq!(latex("e ** x"), "\\mathrm{e}^{x}");
+        assert_eq!(latex("pie + E"), "pie + E");
+        assert_eq!(to_latex(&Integral::with_constant(parse("x").unwrap())), "x + C");
+    }
+
+    #[test]
+    fn complex_numbers() {
+        let complex = |complex: Complex| type_to_latex(&Types::Complex(Wrapper::Constant(complex)));
+
+        assert_eq!(complex(Complex::Exact(Rational::new(0, 1), Rational::new(1, 2))), "\\frac{1}{2}i");
+        assert_eq!(complex(Complex::Exact(Rational::new(1, 3), Rational::new(-1, 2))), "\\frac{1}{3} - \\frac{1}{2}i");
+        assert_eq!(complex(Complex::Exact(Rational::new(2, 1), Rational::new(1, 1))), "2 + i");
+        assert_eq!(complex(Complex::Exact(Rational::new(0, 1), Rational::new(-1, 1))), "-i");
+        assert_eq!(complex(Complex::Exact(Rational::new(-3, 4), Rational::new(0, 1))), "-\\frac{3}{4}");
+        assert_eq!(complex(Complex::approximate(1.5, -2.0)), "1.5 - 2i");
+        assert_eq!(complex(Complex::approximate(0.0, 0.25)), "0.25i");
+    }
+
+    #[test]
+    fn history_is_annotated_with_rules() {
+        let mut state = State::new(parse("1 + 1").unwrap());
+        state.apply(parse("2").unwrap(), "Constant_Evaluation");
+
+        assert_eq!(
+            history_to_latex(&state.history),
+            "\\begin{align*}\n  & 1 + 1 \\\\\n  &= 2 && \\text{Constant\\_Evaluation}\n\\end{align*}\n",
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! Step by step manipulation of mathematical expressions.
+//!
+//! Expressions are trees of typed values and operations. They are built with the
+//! [`parser`] or the constructors of [`types`] and [`operations`], transformed by
+//! applying the rules found by [`rules::find_all_rules`], evaluated exactly with
+//! [`Expressions::solve`], or approximated with [`Expressions::numeric`], and formatted
+//! with `Display`, [`latex`], [`render`] or [`json`].
+//!
+//! ```
+//! use ruth::{parser, rules};
+//!
+//! let expression = parser::parse("x * (a + b)").unwrap();
+//! let matches = rules::find_all_rules(&expression, &[]);
+//!
+//! assert_eq!(matches[0].result.to_string(), "x * b + x * a");
+//! assert_eq!(parser::parse("2 ** 10").unwrap().solve().unwrap().to_string(), "1024");
+//! ```
+
+use std::{fmt::{Debug, Display}, sync::Arc};
+
+pub mod types;
+pub use types::*;
+
+pub mod operations;
+pub use operations::{Addition, BinaryOperation, Derivative, Division, Exponentiation, FunctionCall, Integral, Multiplication, Operation, OperationTrait, Precedence, Product, Subtraction, Sum};
+
+pub mod rules;
+
+pub mod soundness;
+
+pub mod functions;
+
+pub mod constants;
+use constants::find_constant;
+
+pub mod surd;
+use surd::Surd;
+
+pub mod parser;
+
+pub mod definitions;
+use definitions::Definition;
+
+pub mod latex;
+
+pub mod render;
+
+pub mod tree;
+
+pub mod dot;
+
+pub mod arena;
+
+pub mod json;
+
+pub mod session;
+
+pub mod visit;
+use visit::{Fold, Visitor};
+
+pub mod error;
+pub use error::{Error, ErrorKind};
+
+pub mod state;
+use state::{State, Step};
+
+pub mod commands;
+
+/// Node of an expression tree, either a typed value or an operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum InnerExpressions {
+    Type(Types),
+    Operation(Operation),
+}
+
+impl Display for InnerExpressions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InnerExpressions::Type(types) => Display::fmt(&types, f),
+            InnerExpressions::Operation(operation) => Display::fmt(&operation, f),
+        }
+    }
+}
+
+/// Immutable expression tree.
+///
+/// Clones are cheap and share the tree, changes rebuild only the nodes above the change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expressions(pub(crate) Arc<InnerExpressions>);
+
+impl Expressions {
+    /// Wraps a node into a new tree
+    pub fn new(inner: InnerExpressions) -> Self {
+        Self(Arc::new(inner))
+    }
+
+    pub(crate) fn is_operation(&self) -> Option<Operation> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(operation) => Some(operation.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_addition(&self) -> Option<Addition> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Addition(addition)) => Some(addition.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_subtraction(&self) -> Option<Subtraction> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Subtraction(subtraction)) => Some(subtraction.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_multiplication(&self) -> Option<Multiplication> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Multiplication(multiplication)) => Some(multiplication.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_exponentiation(&self) -> Option<Exponentiation> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Exponentiation(exponentiation)) => Some(exponentiation.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_derivative(&self) -> Option<Derivative> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Derivative(derivative)) => Some(derivative.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_integral(&self) -> Option<Integral> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Integral(integral)) => Some(integral.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_function_call(&self) -> Option<FunctionCall> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::FunctionCall(function_call)) => Some(function_call.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_sum(&self) -> Option<Sum> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Sum(sum)) => Some(sum.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_product(&self) -> Option<Product> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(Operation::Product(product)) => Some(product.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_natural(&self) -> Option<Natural> {
+        match self.0.as_ref() {
+            InnerExpressions::Type(Types::Natural(Wrapper::Constant(natural))) => Some(natural.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_complex(&self) -> Option<Complex> {
+        match self.0.as_ref() {
+            InnerExpressions::Type(Types::Complex(Wrapper::Constant(complex))) => Some(complex.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_imaginary_unit(&self) -> bool {
+        self.is_complex().is_some_and(|complex| complex.is_unit())
+    }
+
+    /// Square root of a natural as sqrt(n) or k * sqrt(n), also with nested coefficients like 3 * (2 * sqrt(2))
+    pub(crate) fn is_surd(&self) -> Option<Surd> {
+        let root = self.is_function_call().and_then(|call| call.argument_of("sqrt"));
+
+        if let Some(radicand) = root.and_then(|radicand| radicand.is_natural()) {
+            return Some(Surd { coefficient: 1, radicand: radicand.get() });
+        }
+
+        let mul = self.is_multiplication()?;
+        let surd = mul.right.is_surd()?;
+
+        Some(Surd { coefficient: mul.left.is_natural()?.get().checked_mul(surd.coefficient)?, ..surd })
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        matches!(self.0.as_ref(), InnerExpressions::Type(types) if types.is_zero())
+    }
+
+    pub(crate) fn is_one(&self) -> bool {
+        matches!(self.0.as_ref(), InnerExpressions::Type(types) if types.is_one())
+    }
+
+    pub(crate) fn is_zahl(&self) -> Option<Zahl> {
+        match self.0.as_ref() {
+            InnerExpressions::Type(Types::Zahl(Wrapper::Constant(natural))) => Some(natural.clone()),
+            _ => None,
+        }
+    }
+
+    /// Name of the variable if the expression is a single variable
+    pub fn get_variable(&self) -> Option<String> {
+        match self.0.as_ref() {
+            InnerExpressions::Type(types) => types.get_variable().map(|name| name.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Binding strength used to place parentheses
+    pub fn precedence(&self) -> Precedence {
+        match self.0.as_ref() {
+            InnerExpressions::Type(types) => types.precedence(),
+            InnerExpressions::Operation(operation) => operation.precedence(),
+        }
+    }
+
+    /// Variable bound by a sum, product or definite integral
+    pub fn get_bound_variable(&self) -> Option<String> {
+        match self.0.as_ref() {
+            InnerExpressions::Operation(operation) => operation.get_bound_variable(),
+            _ => None,
+        }
+    }
+
+    /// Whether the variable occurs free in the expression
+    pub fn contains_variable(&self, name: &str) -> bool {
+        match self.0.as_ref() {
+            InnerExpressions::Type(types) => types.get_variable() == Some(name),
+            InnerExpressions::Operation(operation) => {
+                let bound = operation.get_bound_variable();
+
+                operation.get_children().iter().enumerate().any(|(i, child)| {
+                    !(i == 0 && bound.as_deref() == Some(name)) && child.contains_variable(name)
+                })
+            },
+        }
+    }
+
+    /// Names of all variables, free or bound, sorted and without duplicates.
+    /// Named constants like `pi` are not variables
+    pub fn variables(&self) -> Vec<String> {
+        let mut visitor = Variables::default();
+        visitor.visit(self);
+
+        let mut variables = visitor.0;
+        variables.sort();
+        variables.dedup();
+        variables
+    }
+
+    /// Copy of the expression with every free occurrence of the variable replaced
+    pub fn substitute(&self, name: &str, value: &Expressions) -> Expressions {
+        self.substitute_all(&[(name.to_string(), value.clone())])
+    }
+
+    /// Replaces all variables at once, so values are never substituted again
+    pub fn substitute_all(&self, substitutions: &[(String, Expressions)]) -> Expressions {
+        let variable = self.get_variable();
+
+        if let Some((_, value)) = substitutions.iter().find(|(name, _)| Some(name) == variable.as_ref()) {
+            return value.clone();
+        }
+
+        let InnerExpressions::Operation(operation) = self.0.as_ref() else {
+            return self.clone();
+        };
+
+        let Some(bound) = operation.get_bound_variable() else {
+            return self.map_children(&mut |_, child| child.substitute_all(substitutions));
+        };
+
+        // Only the first child is in the scope of the bound variable
+        let scoped: Vec<(String, Expressions)> = substitutions.iter()
+            .filter(|(name, _)| name != &bound)
+            .cloned()
+            .collect();
+
+        let mut operation = operation.clone();
+
+        // Rename the bound variable if it would capture a substituted one
+        if scoped.iter().any(|(_, value)| value.contains_variable(&bound)) {
+            let body = &operation.get_children()[0];
+            let fresh = (1..)
+                .map(|i| format!("{bound}{i}"))
+                .find(|name| !body.contains_variable(name) && !scoped.iter().any(|(_, value)| value.contains_variable(name)))
+                .unwrap();
+
+            let renaming = [(bound, WrappedReal::new_variable(&fresh).into())];
+            operation = operation.map_children(&mut |i, child| if i == 0 { child.substitute_all(&renaming) } else { child.clone() });
+            operation.set_bound_variable(&fresh);
+        }
+
+        operation.map_children(&mut |i, child| child.substitute_all(if i == 0 { &scoped } else { substitutions })).into()
+    }
+
+    /// Same expression with every child replaced, children are passed with their index
+    pub fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Expressions {
+        match self.0.as_ref() {
+            InnerExpressions::Type(_) => self.clone(),
+            InnerExpressions::Operation(operation) => operation.map_children(f).into(),
+        }
+    }
+
+    /// Subexpression at the path of child indices
+    pub fn at(&self, path: &[usize]) -> Option<Expressions> {
+        match path {
+            [] => Some(self.clone()),
+            [index, rest @ ..] => self.get_children().get(*index)?.at(rest),
+        }
+    }
+
+    /// Expression with the subexpression at the path replaced,
+    /// sharing everything except the nodes on the path
+    pub fn replace_at(&self, path: &[usize], replacement: Expressions) -> Expressions {
+        let Some((index, rest)) = path.split_first() else {
+            return replacement;
+        };
+
+        let mut replacement = Some(replacement);
+        self.map_children(&mut |i, child| match replacement.take() {
+            Some(replacement) if i == *index => child.replace_at(rest, replacement),
+            other => {
+                replacement = other;
+                child.clone()
+            }
+        })
+    }
+
+    /// Copy of the root node, children stay shared
+    pub fn to_inner(&self) -> InnerExpressions {
+        self.0.as_ref().clone()
+    }
+}
+
+// {:#} annotates every node with its type, {:+} parenthesizes every operation
+impl Display for Expressions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            match self.0.as_ref() {
+                InnerExpressions::Type(types) => write!(f, "({:#}: {})", types, types.get_type()),
+                InnerExpressions::Operation(operation) => match self.solve() {
+                    Ok(types) => write!(f, "({:#}: {})", operation, types.get_type()),
+                    Err(_) => write!(f, "({:#}: Error)", operation),
+                },
+            }
+        } else {
+            // Verbose mode parenthesizes everything that is not an atom
+            let parenthesize = f.sign_plus() && self.precedence() != Precedence::Atom;
+
+            if parenthesize {
+                write!(f, "(")?;
+            }
+
+            match self.0.as_ref() {
+                InnerExpressions::Type(types) => write!(f, "{}", types)?,
+                InnerExpressions::Operation(operation) => Display::fmt(operation, f)?,
+            }
+
+            if parenthesize {
+                write!(f, ")")?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+impl Expressions {
+    /// Direct subexpressions, sharing the tree
+    pub fn get_children(&self) -> Vec<Expressions> {
+        match self.0.as_ref() {
+            InnerExpressions::Type(types) => types.get_children(),
+            InnerExpressions::Operation(operation) => operation.get_children(),
+        }
+    }
+
+    /// Evaluates the expression as far as possible
+    pub fn solve(&self) -> Result<Types, Error> {
+        Solve.fold(self)
+    }
+
+    /// Evaluates the expression to a real or complex number where possible,
+    /// including named constants and functions of exact arguments like `sqrt(2)`
+    pub fn numeric(&self) -> Result<Types, Error> {
+        self.numeric_with_precision(0)
+    }
+
+    /// Like numeric, with reals of the number of significant decimal digits, 0 computes with f64
+    pub fn numeric_with_precision(&self, digits: usize) -> Result<Types, Error> {
+        types::with_precision(digits, || Numeric.fold(self))
+    }
+
+    /// Evaluates the expression as the child with the index of its parent,
+    /// so errors point into the parent
+    pub fn solve_child(&self, index: usize) -> Result<Types, Error> {
+        self.solve().map_err(|error| error.in_child(index))
+    }
+}
+
+// Evaluates the children before their parent, errors are located at the failing child
+struct Solve;
+
+impl Fold for Solve {
+    type Output = Result<Types, Error>;
+
+    fn fold_type(&mut self, types: &Types) -> Self::Output {
+        Ok(types.solve())
+    }
+
+    fn fold_operation(&mut self, operation: &Operation, children: Vec<Self::Output>) -> Self::Output {
+        operation.evaluate(located(children)?).map(Types::demote)
+    }
+}
+
+// Values of the children, or the first error located at its child
+fn located(children: Vec<Result<Types, Error>>) -> Result<Vec<Types>, Error> {
+    children.into_iter()
+        .enumerate()
+        .map(|(i, child)| child.map_err(|error| error.in_child(i)))
+        .collect()
+}
+
+// Evaluates like Solve, but replaces named constants by their value
+// and approximates the exact arguments of functions, which Solve keeps symbolic
+struct Numeric;
+
+impl Fold for Numeric {
+    type Output = Result<Types, Error>;
+
+    fn fold_type(&mut self, types: &Types) -> Self::Output {
+        match types.get_variable().and_then(find_constant) {
+            Some(constant) => Ok(Types::Real(Wrapper::Constant((constant.value)()))),
+            None => Ok(types.solve()),
+        }
+    }
+
+    fn fold_operation(&mut self, operation: &Operation, children: Vec<Self::Output>) -> Self::Output {
+        let mut children = located(children)?;
+
+        if let Operation::FunctionCall(_) = operation {
+            children = children.into_iter()
+                .map(|child| if child.is_value() { child.promote(Level::Real) } else { child })
+                .collect();
+        }
+
+        operation.evaluate_with(children, |term| Numeric.fold(term)).map(Types::demote)
+    }
+}
+
+// Collects free and bound variable names
+#[derive(Default)]
+struct Variables(Vec<String>);
+
+impl Visitor for Variables {
+    fn visit_type(&mut self, types: &Types) {
+        self.0.extend(types.get_variable().filter(|name| find_constant(name).is_none()).map(str::to_string));
+    }
+
+    fn visit_operation(&mut self, operation: &Operation) {
+        self.0.extend(operation.get_bound_variable());
+        visit::walk_operation(self, operation);
+    }
+}
+
+impl<T: Into<InnerExpressions>> From<T> for Expressions {
+    fn from(t: T) -> Self {
+        Expressions::new(t.into())
+    }
+}
+
+pub(crate) struct Rule {
+    matches: fn(&Expressions) -> Option<InnerExpressions>,
+    name: &'static str,
+    description: &'static str,
+}
+
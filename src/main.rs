@@ -1,329 +1,115 @@
-use std::{cell::RefCell, fmt::{Debug, Display}, ops::Deref, rc::Rc};
+use std::io::IsTerminal;
 
-mod types;
-use types::*;
-
-pub mod operations;
-use operations::{Addition, BinaryOperation, Exponentiation, Multiplication, Operation, OperationTrait};
-
-mod rules;
-
-#[derive(Debug, Clone)]
-enum InnerExpressions {
-    Type(Types),
-    Operation(Operation),
-}
-
-impl Display for InnerExpressions {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            InnerExpressions::Type(types) => Display::fmt(&types, f),
-            InnerExpressions::Operation(operation) => Display::fmt(&operation, f),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct Expressions(Rc<RefCell<InnerExpressions>>);
+use ruth::{
+    commands::{run_command, run_script, Flow, HELP_COMMAND},
+    state::State,
+    Addition, BinaryOperation, Error, Exponentiation, Expressions, WrappedNatural, WrappedReal,
+};
 
-impl Expressions {
-    fn new(inner: InnerExpressions) -> Self {
-        Self(Rc::new(RefCell::new(inner)))
-    }
+mod cli;
 
-    fn is_operation(&self) -> Option<Operation> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Operation(operation) => Some(operation.clone()),
-            _ => None,
-        }
-    }
+mod editor;
 
-    fn is_addition(&self) -> Option<Addition> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Operation(Operation::Addition(addition)) => Some(addition.clone()),
-            _ => None,
-        }
-    }
+fn main() {
+    let equation: Expressions = Exponentiation::new(
+        Addition::new(WrappedReal::new_variable("b").into(), WrappedReal::new_variable("c").into()).into(),
+        WrappedNatural::new(2).into(), 
+    ).into();
 
-    fn is_multiplication(&self) -> Option<Multiplication> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Operation(Operation::Multiplication(multiplication)) => Some(multiplication.clone()),
-            _ => None,
-        }
-    }
+    let mut state = State::new(equation);
 
-    fn is_exponentiation(&self) -> Option<Exponentiation> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Operation(Operation::Exponentiation(exponentiation)) => Some(exponentiation.clone()),
-            _ => None,
-        }
-    }
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    fn is_zahl(&self) -> Option<Zahl> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Type(Types::Zahl(Wrapper::Constant(natural))) => Some(natural.clone()),
-            _ => None,
-        }
-    }
-
-    fn to_inner(&self) -> InnerExpressions {
-        self.0.as_ref().borrow().clone()
-    }
-}
-
-impl Display for Expressions {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            match self.0.as_ref().borrow().deref() {
-                InnerExpressions::Type(types) => write!(f, "({:#}: {})", types, types.get_type()),
-                InnerExpressions::Operation(operation) => write!(f, "({:#}: {})", operation, operation.solve().unwrap().get_type()),
+    match args.as_slice() {
+        [flag, file] if flag == "--script" => match std::fs::read_to_string(file) {
+            Ok(script) => exit_on_error(run_script(&mut state, script.lines())),
+            Err(error) => {
+                eprintln!("Could not read {file}: {error}");
+                std::process::exit(1);
             }
-        } else {
-            match self.0.as_ref().borrow().deref() {
-                InnerExpressions::Type(types) => write!(f, "{}", types),
-                InnerExpressions::Operation(operation) => write!(f, "{}", operation),
-            }
-        }
+        },
+        [] if !std::io::stdin().is_terminal() => {
+            let input = std::io::read_to_string(std::io::stdin()).unwrap_or_else(|error| {
+                eprintln!("Could not read input: {error}");
+                std::process::exit(1);
+            });
+            exit_on_error(run_script(&mut state, input.lines()));
+        },
+        [] => run_interactive(&mut state),
+        [subcommand, rest @ ..] if cli::SUBCOMMANDS.contains(&subcommand.as_str()) => {
+            std::process::exit(cli::run(subcommand, rest));
+        },
+        _ => std::process::exit(cli::usage()),
     }
 }
 
-impl Expressions {
-    fn get_children(&self) -> Vec<Expressions> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Type(types) => types.get_children(),
-            InnerExpressions::Operation(operation) => operation.get_children(),
-        }
-    }
-
-    fn copy(&self) -> Expressions {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Type(types) => types.copy(),
-            InnerExpressions::Operation(opeartion) => opeartion.copy(),
-        }
-    }
-
-    fn solve(&self) -> Result<Types, String> {
-        match self.0.as_ref().borrow().deref() {
-            InnerExpressions::Type(types) => Ok(types.solve()),
-            InnerExpressions::Operation(opeartion) => opeartion.solve(),
-        }
+fn exit_on_error(result: Result<(), Error>) {
+    if let Err(error) = result {
+        eprintln!("{error}");
+        std::process::exit(1);
     }
 }
 
-impl<T: Into<InnerExpressions>> From<T> for Expressions {
-    fn from(t: T) -> Self {
-        Expressions::new(t.into())
-    }
-}
-
-struct Rule {
-    matches: &'static dyn Fn(&Expressions) -> Option<InnerExpressions>,
-    name: &'static str,
-    description: &'static str,
-}
-
-unsafe impl Send for Rule {}
-unsafe impl Sync for Rule {}
-
-struct State {
-    // Current working equation
-    current: Expressions,
-
-    // Selction of current equation
-    selection: Expressions,
-
-    // History of equations, just copies
-    history: Vec<Expressions>,
-}
-
-struct Command {
-    name: &'static str,
-    description: &'static str,
-    usage: &'static str,
-
-    execute: &'static dyn Fn(&mut State, &[&str]),
-}
-
-unsafe impl Send for Command {}
-unsafe impl Sync for Command {}
-
-static HELP_COMMAND: Command = Command {
-    name: "help",
-    description: "Prints this help page",
-    usage: "",
-
-    execute: &|_state: &mut State, _args: &[&str]| {
-        println!("Commands:");
-        for command in COMMANDS.iter() {
-            let start = format!("{} {}", command.name, command.usage);
-            println!("    {:20}: {}", start, command.description);
-        }
-    },
-};
-
-static HISTORY_COMMAND: Command = Command {
-    name: "history",
-    description: "Prints all equations history",
-    usage: "",
-
-    execute: &|state: &mut State, _args: &[&str]| {
-        println!("History:");
-        for point in state.history.iter() {
-            println!("{}", point);
-        }
-    },
-};
-
-static CHILDREN_COMMAND: Command = Command {
-    name: "children",
-    description: "Prints current children or select child by index",
-    usage: "[<index> | top]",
-
-    execute: &|state: &mut State, args: &[&str]| {
-        let children = state.selection.get_children();
-
-        if let Some(index) = args.first() {
-            if index == &"top" {
-                state.selection = state.current.clone();
-                return;
-            }
-
-            match index.parse::<usize>() {
-                Ok(index) => {    
-                    if index < children.len() {
-                        state.selection = children[index].clone();
-                    } else {
-                        println!("Index: {index} out of range: {}", children.len());
-                    }
-                },
-                Err(error) =>{
-                    println!("Invalid index: {error}");
-                }
-            }
-        } else {
-            println!("Current children");
-            for (i, child) in children.iter().enumerate() {
-                println!("{i}: {}", child);
-            }
+fn run_interactive(state: &mut State) {
+    let mut editor = match rustyline::Editor::<editor::ReplHelper, rustyline::history::DefaultHistory>::new() {
+        Ok(editor) => editor,
+        Err(error) => {
+            eprintln!("Could not start the line editor: {error}");
+            std::process::exit(1);
         }
-    }
-};
-
-static TYPE_COMMAND: Command = Command {
-    name: "type",
-    description: "Prints current equation with types",
-    usage: "",
-
-    execute: &|state: &mut State, _args: &[&str]| {
-        println!("{:#}", state.selection);
-    }
-};
-
-static RULES_COMMAND: Command = Command {
-    name: "rules",
-    description: "Prints all rules",
-    usage: "[<index>]",
-
-    execute: &|state: &mut State, args: &[&str]| {
-        let rules = rules::find_all_rules(&state.selection);
-
-        if let Some(index) = args.first() {
-            match index.parse::<usize>() {
-                Ok(index) => {    
-                    if index < rules.len() {
-                        println!("Applying rule: {}", rules[index]);
-                        *state.selection.0.borrow_mut() = rules[index].result();
-
-                        state.history.push(state.current.clone());
-
-                        // detach from history
-                        state.current = state.current.copy();
-                        state.selection = state.current.clone();
-                    } else {
-                        println!("Index: {index} out of range: {}", rules.len());
-                    }
-                },
-                Err(error) =>{
-                    println!("Invalid index: {error}");
-                }
-            }
-        } else {
-            println!("Current applicable rules");
-            for (i, rule) in rules.iter().enumerate() {
-                println!("{i}: {}", rule);
-            }
-        }
-    }
-};
-
-static EXIT_COMMAND: Command = Command {
-    name: "q",
-    description: "Exits the program",
-    usage: "",
+    };
+    editor.set_helper(Some(editor::ReplHelper::default()));
 
-    execute: &|_state: &mut State, _args: &[&str]| {
-        std::process::exit(0);
+    let history = editor::history_path();
+    if let Some(history) = &history {
+        // a missing history file is expected on the first run
+        let _ = editor.load_history(history);
     }
-};
 
-static COMMANDS: [&Command; 6] = [
-    &HELP_COMMAND,
-    &HISTORY_COMMAND,
-    &CHILDREN_COMMAND,
-    &TYPE_COMMAND,
-    &RULES_COMMAND,
-    &EXIT_COMMAND,
-];
+    (HELP_COMMAND.execute)(state, &[]).unwrap();
 
-fn main() {
-    // let equation: Expressions = Multiplication::new(Natural::new(2).into(), Addition::new(Real::new(3.0).into(), Real::new(4.0).into()).into()).into();
-    
-    let equation: Expressions = Exponentiation::new(
-        Addition::new(WrappedReal::new_variable("b").into(), WrappedReal::new_variable("c").into()).into(),
-        WrappedNatural::new(2).into(), 
-    ).into();
-
-    // let equation: Expressions = Subtraction::new(Natural::new(5).into(), Natural::new(6).into()).into();
-
-    let mut state = State {
-        history: vec![equation.copy()],
-        selection: equation.clone(),
-        current: equation,
-    };
-
-    (HELP_COMMAND.execute)(&mut state, &[]);
-
-    'outer: loop {
+    loop {
         println!();
         println!();
-        println!("Equation: {}", state.current);
-        println!("Selection: {}", state.selection);
+        state.print_equation();
 
+        if let Some(helper) = editor.helper_mut() {
+            helper.update(state);
+        }
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-        let mut input = input.split_whitespace();
+        let input = match editor.readline("> ") {
+            Ok(input) => input,
+            Err(rustyline::error::ReadlineError::Interrupted) => continue,
+            Err(rustyline::error::ReadlineError::Eof) => break,
+            Err(error) => {
+                eprintln!("Could not read input: {error}");
+                std::process::exit(1);
+            }
+        };
 
-        let command_name = input.next().unwrap_or("");
-        let args: Vec<&str> = input.collect();
+        if !input.trim().is_empty() {
+            let _ = editor.add_history_entry(input.as_str());
+        }
 
         println!();
         println!();
-        println!("Equation: {}", state.current);
-        println!("Selection: {}", state.selection);
-        println!("Command: {}", command_name);
+        state.print_equation();
+        println!("Command: {}", input.trim());
         println!();
 
-        for command in COMMANDS.iter() {
-            if command.name == command_name {
-                (command.execute)(&mut state, &args);
-                continue 'outer;
-            }
+        match run_command(state, &input) {
+            Ok(Flow::Continue) => {},
+            Ok(Flow::Exit) => break,
+            Err(error) => {
+                println!("{error}");
+                println!("enter <help> for help");
+            },
         }
+    }
 
-        if !command_name.is_empty() {
-            println!("Unknown command: {}", command_name);
-            println!("enter <help> for help")
+    if let Some(history) = &history {
+        if let Err(error) = editor.save_history(history) {
+            eprintln!("Could not save input history: {error}");
         }
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,5 @@
+//! Operations of expression trees and their evaluation.
+
 use std::fmt::Display;
 
 mod addition;
@@ -9,18 +11,107 @@ pub use subtraction::*;
 mod multiplication;
 pub use multiplication::*;
 
+mod division;
+pub use division::*;
+
 mod exponention;
 pub use exponention::*;
 
+mod derivative;
+pub use derivative::*;
+
+mod integral;
+pub use integral::*;
+
+mod function_call;
+pub use function_call::*;
+
+mod sum;
+pub use sum::*;
+
+mod product;
+pub use product::*;
+
 use enum_dispatch::enum_dispatch;
 
-use crate::{Expressions, InnerExpressions, Types};
+use crate::{Error, Expressions, InnerExpressions, Types};
+
+/// Binding strength of an expression, used to omit parentheses when printing.
+/// Binders like sums and integrals extend as far right as possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Binder,
+    Sum,
+    Product,
+    Power,
+    Atom,
+}
+
+// Writes a child with the flags of the formatter, {:#} adds types and {:+} all parentheses
+fn fmt_child(f: &mut std::fmt::Formatter<'_>, child: &Expressions) -> std::fmt::Result {
+    if f.alternate() {
+        write!(f, "{:#}", child)
+    } else if f.sign_plus() {
+        write!(f, "{:+}", child)
+    } else {
+        write!(f, "{}", child)
+    }
+}
+
+// Writes an operand in parentheses if it binds weaker than the operation requires
+fn fmt_operand(f: &mut std::fmt::Formatter<'_>, operand: &Expressions, required: Precedence) -> std::fmt::Result {
+    if !f.alternate() && !f.sign_plus() && operand.precedence() < required {
+        write!(f, "({})", operand)
+    } else {
+        fmt_child(f, operand)
+    }
+}
+
+// Writes the body of a binder, which can be another binder without parentheses
+fn fmt_body(f: &mut std::fmt::Formatter<'_>, body: &Expressions) -> std::fmt::Result {
+    if body.precedence() == Precedence::Binder {
+        fmt_operand(f, body, Precedence::Binder)
+    } else {
+        fmt_operand(f, body, Precedence::Product)
+    }
+}
+
+// Values of the left and right operand of a binary operation
+fn binary(children: Vec<Types>) -> [Types; 2] {
+    children.try_into().unwrap_or_else(|_| unreachable!("binary operations have two children"))
+}
 
 #[enum_dispatch]
 pub trait OperationTrait: Into<Operation> + Display {
     fn get_children(&self) -> Vec<Expressions>;
-    fn copy(&self) -> Expressions;
-    fn solve(&self) -> Result<Types, String>;
+
+    // Same operation with every child replaced, children are passed with their index
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation;
+
+    // Value of the operation from the values of its children, in the order of get_children
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error>;
+
+    // Like evaluate, with the terms only known during evaluation, like those of a sum, evaluated by solve
+    fn evaluate_with(&self, children: Vec<Types>, _solve: fn(&Expressions) -> Result<Types, Error>) -> Result<Types, Error> {
+        self.evaluate(children)
+    }
+
+    // Variable bound inside the first child, like the index of a sum
+    fn get_bound_variable(&self) -> Option<String> {
+        None
+    }
+
+    fn set_bound_variable(&mut self, _name: &str) {}
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Atom
+    }
+
+    // Writes the operation with its own Display, dispatched like the other methods
+    // so that Display of Operation needs no arm per variant
+    fn fmt_operation(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
 }
 
 pub trait BinaryOperation: OperationTrait {
@@ -33,22 +124,67 @@ impl<T: Into<Operation>> From<T> for InnerExpressions {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[enum_dispatch(OperationTrait)]
 pub enum Operation {
     Addition(Addition),
     Multiplication(Multiplication),
     Exponentiation(Exponentiation),
     Subtraction(Subtraction),
+    Division(Division),
+    Derivative(Derivative),
+    Integral(Integral),
+    FunctionCall(FunctionCall),
+    Sum(Sum),
+    Product(Product),
 }
 
 impl Display for Operation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Operation::Addition(addition) => Display::fmt(&addition, f),
-            Operation::Multiplication(multiplication) => Display::fmt(&multiplication, f),
-            Operation::Exponentiation(exponention) => Display::fmt(&exponention, f),
-            Operation::Subtraction(subtraction) => Display::fmt(&subtraction, f),
-        }
+        self.fmt_operation(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    fn printed(input: &str) -> String {
+        parse(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn minimal_parentheses() {
+        assert_eq!(printed("(a + b) + c"), "a + b + c");
+        assert_eq!(printed("a + (b + c)"), "a + (b + c)");
+        assert_eq!(printed("(a * b) * c"), "a * b * c");
+        assert_eq!(printed("a * (b * c)"), "a * (b * c)");
+        assert_eq!(printed("(a + b) * c"), "(a + b) * c");
+        assert_eq!(printed("(a ** b) ** c"), "(a ** b) ** c");
+        assert_eq!(printed("a ** (b * c)"), "a ** (b * c)");
+        assert_eq!(printed("2 * (1/2)"), "2 * (1/2)");
+    }
+
+    #[test]
+    fn negative_constants_are_parenthesized() {
+        assert_eq!(printed("x * -2"), "x * (-2)");
+        assert_eq!(printed("-2 + x"), "-2 + x");
+    }
+
+    #[test]
+    fn binder_bodies() {
+        assert_eq!(printed("sum(k, 1, n, k + 1)"), "sum[k = 1, n] (k + 1)");
+        assert_eq!(printed("sum(k, 1, n, prod(j, 1, k, j))"), "sum[k = 1, n] prod[j = 1, k] j");
+    }
+
+    #[test]
+    fn verbose_display() {
+        assert_eq!(format!("{:+}", parse("a + b * c ** 2").unwrap()), "(a + (b * (c ** 2)))");
+        assert_eq!(format!("{:+}", parse("f(x + 1) - sum(k, 1, n + 1, 2 * k)").unwrap()), "(f((x + 1)) - (sum[k = 1, (n + 1)] (2 * k)))");
+    }
+
+    #[test]
+    fn typed_display() {
+        assert_eq!(format!("{:#}", parse("1 + x").unwrap()), "((1: Natural) + (x: Real): Real)");
     }
 }
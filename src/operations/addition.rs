@@ -1,14 +1,14 @@
 use std::fmt::Display;
 
-use crate::{Expressions, Types, Wrapper};
+use crate::{dispatch, Error, Expressions, Types, Wrapper};
 use super::BinaryOperation;
 
-use super::OperationTrait;
+use super::{binary, fmt_operand, Operation, OperationTrait, Precedence};
 
 pub trait Add<Rhs = Self> where {
     type Output;
 
-    fn add(self, other: Rhs) -> Result<Self::Output, String>;
+    fn add(self, other: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl<L, R, O> Add<Wrapper<R>> for Wrapper<L> where
@@ -20,7 +20,7 @@ impl<L, R, O> Add<Wrapper<R>> for Wrapper<L> where
 {
     type Output = Types;
 
-    fn add(self, rhs: Wrapper<R>) -> Result<Self::Output, String> {
+    fn add(self, rhs: Wrapper<R>) -> Result<Self::Output, Error> {
         match (self, rhs) {
             (Wrapper::<L>::Constant(lhs), Wrapper::<R>::Constant(rhs)) => Ok((lhs.add(rhs))?.into().into()),
             (lhs, rhs) => Ok(Wrapper::<O>::Expression(Addition::new(lhs.into(), rhs.into()).into()).into()),
@@ -28,11 +28,7 @@ impl<L, R, O> Add<Wrapper<R>> for Wrapper<L> where
     }
 }
 
-pub trait TypeAdd {
-    fn type_add(self, right: Types) -> Result<Types, String>;
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Addition {
     pub left: Expressions,
     pub right: Expressions,
@@ -40,11 +36,9 @@ pub struct Addition {
 
 impl Display for Addition {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            write!(f, "({:#} + {:#})", self.left, self.right)
-        } else {
-            write!(f, "({} + {})", self.left, self.right)
-        }
+        fmt_operand(f, &self.left, Precedence::Sum)?;
+        write!(f, " + ")?;
+        fmt_operand(f, &self.right, Precedence::Product)
     }
 }
 
@@ -53,16 +47,17 @@ impl OperationTrait for Addition {
         vec![self.left.clone(), self.right.clone()]
     }
 
-    fn copy(&self) -> Expressions {
-        Addition::new(self.left.copy(), self.right.copy()).into()
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Addition::new(f(0, &self.left), f(1, &self.right)).into()
     }
 
-    fn solve(&self) -> Result<Types, String> {
-        match self.left.solve()? {
-            Types::Real(left) => Ok((left.type_add(self.right.solve()?))?),
-            Types::Natural(left) => Ok((left.type_add(self.right.solve()?))?),
-            Types::Zahl(left) => Ok((left.type_add(self.right.solve()?))?),
-        }
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let [left, right] = binary(children);
+        dispatch!(left.common(right), |left, right| left.add(right))
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Sum
     }
 }
 
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use crate::{Error, Expressions, Types, WrappedNatural, WrappedReal, TypeTrait};
+
+use super::{fmt_body, Operation, OperationTrait, Precedence};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Derivative {
+    pub expression: Expressions,
+    pub variable: String,
+}
+
+impl Derivative {
+    pub fn new(expression: Expressions, variable: &str) -> Self {
+        Self { expression, variable: variable.to_string() }
+    }
+}
+
+impl Display for Derivative {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "d/d{} ", self.variable)?;
+        fmt_body(f, &self.expression)
+    }
+}
+
+impl OperationTrait for Derivative {
+    fn get_children(&self) -> Vec<Expressions> {
+        vec![self.expression.clone()]
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Derivative::new(f(0, &self.expression), &self.variable).into()
+    }
+
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        // The derivative of a constant is always 0
+        if children[0].is_value() {
+            Ok(WrappedNatural::new(0).into())
+        } else {
+            Ok(WrappedReal::Expression(self.clone().into()).into())
+        }
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Binder
+    }
+}
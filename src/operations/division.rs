@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use crate::{Common, Error, Expressions, Level, Types, Wrapper};
+use super::BinaryOperation;
+
+use super::{binary, fmt_operand, Operation, OperationTrait, Precedence};
+
+pub trait Div<Rhs = Self> {
+    type Output;
+
+    fn div(self, other: Rhs) -> Result<Self::Output, Error>;
+}
+
+impl<L, R, O> Div<Wrapper<R>> for Wrapper<L> where
+    Wrapper<L>: Into<Expressions>,
+    Wrapper<R>: Into<Expressions>,
+    Wrapper<O>: Into<Types>,
+    O: Into<Wrapper<O>>,
+    L: Div<R, Output = O>,
+{
+    type Output = Types;
+
+    fn div(self, rhs: Wrapper<R>) -> Result<Self::Output, Error> {
+        match (self, rhs) {
+            (Wrapper::<L>::Constant(lhs), Wrapper::<R>::Constant(rhs)) => Ok((lhs.div(rhs))?.into().into()),
+            (lhs, rhs) => Ok(Wrapper::<O>::Expression(Division::new(lhs.into(), rhs.into()).into()).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Division {
+    pub left: Expressions,
+    pub right: Expressions,
+}
+
+impl Display for Division {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_operand(f, &self.left, Precedence::Product)?;
+        write!(f, " / ")?;
+        fmt_operand(f, &self.right, Precedence::Power)
+    }
+}
+
+impl OperationTrait for Division {
+    fn get_children(&self) -> Vec<Expressions> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Division::new(f(0, &self.left), f(1, &self.right)).into()
+    }
+
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let [left, right] = binary(children);
+
+        // Quotients of integers are fractions
+        match left.promote(Level::Rational).common(right) {
+            Common::Rational(left, right) => left.div(right),
+            Common::Real(left, right) => left.div(right),
+            Common::Complex(left, right) => left.div(right),
+            _ => unreachable!("operands are promoted to at least a fraction"),
+        }
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Product
+    }
+}
+
+impl BinaryOperation for Division {
+    fn new(left: Expressions, right: Expressions) -> Self {
+        Self { left, right }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    fn evaluate(expression: &str) -> (String, String) {
+        let result = parse(expression).unwrap().solve().unwrap();
+        (result.to_string(), result.get_type().to_string())
+    }
+
+    #[test]
+    fn quotients_of_integers_are_fractions() {
+        assert_eq!(evaluate("6 / 4"), ("3/2".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("(0 - 6) / 3"), ("-2".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("2 ** 3 / 4"), ("2".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("1 / (1/2)"), ("2".to_string(), "Rational".to_string()));
+    }
+
+    #[test]
+    fn other_quotients() {
+        assert_eq!(evaluate("1.5 / 0.5"), ("3".to_string(), "Real".to_string()));
+        assert_eq!(evaluate("1 / i"), ("-i".to_string(), "Complex".to_string()));
+        assert_eq!(evaluate("x / 2").0, "x / 2");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("1 / (1 - 1)").unwrap().solve().unwrap_err().to_string(), "Division by zero");
+        assert_eq!(parse("1.5 / 0.0").unwrap().solve().unwrap_err().to_string(), "Division by zero");
+    }
+}
@@ -1,14 +1,14 @@
 use std::fmt::Display;
 
-use crate::{Expressions, Types, Wrapper};
+use crate::{dispatch, Common, Error, Expressions, Level, Types, Wrapper};
 use super::BinaryOperation;
 
-use super::OperationTrait;
+use super::{binary, fmt_operand, Operation, OperationTrait, Precedence};
 
 pub trait Exp<Rhs = Self> where {
     type Output;
 
-    fn exp(self, other: Rhs) -> Result<Self::Output, String>;
+    fn exp(self, other: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl<L, R, O> Exp<Wrapper<R>> for Wrapper<L> where
@@ -20,7 +20,7 @@ impl<L, R, O> Exp<Wrapper<R>> for Wrapper<L> where
 {
     type Output = Types;
 
-    fn exp(self, rhs: Wrapper<R>) -> Result<Self::Output, String> {
+    fn exp(self, rhs: Wrapper<R>) -> Result<Self::Output, Error> {
         match (self, rhs) {
             (Wrapper::<L>::Constant(lhs), Wrapper::<R>::Constant(rhs)) => Ok((lhs.exp(rhs))?.into().into()),
             (lhs, rhs) => Ok(Wrapper::<O>::Expression(Exponentiation::new(lhs.into(), rhs.into()).into()).into()),
@@ -28,11 +28,7 @@ impl<L, R, O> Exp<Wrapper<R>> for Wrapper<L> where
     }
 }
 
-pub trait TypeExp {
-    fn type_exp(self, right: Types) -> Result<Types, String>;
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Exponentiation {
     pub left: Expressions,
     pub right: Expressions,
@@ -40,11 +36,9 @@ pub struct Exponentiation {
 
 impl Display for Exponentiation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            write!(f, "({:#} ** {:#})", self.left, self.right)
-        } else {
-            write!(f, "({} ** {})", self.left, self.right)
-        }
+        fmt_operand(f, &self.left, Precedence::Atom)?;
+        write!(f, " ** ")?;
+        fmt_operand(f, &self.right, Precedence::Power)
     }
 }
 
@@ -53,21 +47,72 @@ impl OperationTrait for Exponentiation {
         vec![self.left.clone(), self.right.clone()]
     }
 
-    fn copy(&self) -> Expressions {
-        Exponentiation::new(self.left.copy(), self.right.copy()).into()
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Exponentiation::new(f(0, &self.left), f(1, &self.right)).into()
     }
 
-    fn solve(&self) -> Result<Types, String> {
-        match self.left.solve()? {
-            Types::Real(left) => Ok((left.type_exp(self.right.solve()?))?),
-            Types::Natural(left) => Ok((left.type_exp(self.right.solve()?))?),
-            Types::Zahl(left) => Ok((left.type_exp(self.right.solve()?))?),
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let [left, right] = binary(children);
+
+        // Integer powers stay in the type of the base, negative powers of integers are fractions
+        if right.is_integer() && right.level() <= Level::Rational {
+            let level = if right.is_negative() { left.level().max(Level::Rational) } else { left.level() };
+            return dispatch!(left.promote(level).common(right), |left, right| left.exp(right));
+        }
+
+        // Fractional powers of negative numbers are complex
+        let level = if left.is_negative() && !right.is_integer() { Level::Complex } else { Level::Real };
+
+        match left.promote(level).common(right.promote(level)) {
+            Common::Real(left, right) => left.exp(right),
+            Common::Complex(left, right) => left.exp(right),
+            _ => unreachable!("operands are promoted to real or complex"),
         }
     }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Power
+    }
 }
 
 impl BinaryOperation for Exponentiation {
     fn new(left: Expressions, right: Expressions) -> Self {
         Self { left, right }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    fn evaluate(expression: &str) -> (String, String) {
+        let result = parse(expression).unwrap().solve().unwrap();
+        (result.to_string(), result.get_type().to_string())
+    }
+
+    #[test]
+    fn integer_powers_stay_exact() {
+        assert_eq!(evaluate("2 ** 10"), ("1024".to_string(), "Natural".to_string()));
+        assert_eq!(evaluate("(0 - 2) ** 3"), ("-8".to_string(), "Zahl".to_string()));
+        assert_eq!(evaluate("2 ** (0 - 1)"), ("1/2".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("(0 - 2) ** (0 - 3)"), ("-1/8".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("(2/3) ** (0 - 2)"), ("9/4".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("(1 + i) ** 4"), ("-4".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("i ** (0 - 1)"), ("-i".to_string(), "Complex".to_string()));
+        assert_eq!(evaluate("sum(k, 1, 3, k ** (0 - 1))").0, "11/6");
+    }
+
+    #[test]
+    fn other_powers_are_approximated() {
+        assert_eq!(evaluate("2.5 ** 2"), ("6.25".to_string(), "Real".to_string()));
+        assert_eq!(evaluate("4 ** 0.5"), ("2".to_string(), "Real".to_string()));
+        assert_eq!(evaluate("(0 - 4) ** 0.5").0, "2 * i");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("0 ** (0 - 1)").unwrap().solve().unwrap_err().to_string(), "Division by zero");
+        assert_eq!(parse("2 ** 40").unwrap().solve().unwrap_err().to_string(), "Overflow in 2 ** 40");
+        assert_eq!(parse("(0 - 2) ** 32").unwrap().solve().unwrap_err().to_string(), "Overflow in (-2) ** 32");
+    }
+}
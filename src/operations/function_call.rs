@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use crate::{functions::find_function, Error, Expressions, Types, Wrapper, WrappedReal};
+
+use super::{fmt_child, Operation, OperationTrait};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionCall {
+    pub name: String,
+    pub args: Vec<Expressions>,
+}
+
+impl FunctionCall {
+    pub fn new(name: &str, args: Vec<Expressions>) -> Self {
+        Self { name: name.to_string(), args }
+    }
+
+    /// Argument of a single argument call to the given function
+    pub fn argument_of(&self, name: &str) -> Option<Expressions> {
+        match self.args.as_slice() {
+            [argument] if self.name == name => Some(argument.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.name)?;
+
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            fmt_child(f, arg)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl OperationTrait for FunctionCall {
+    fn get_children(&self) -> Vec<Expressions> {
+        self.args.clone()
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        FunctionCall::new(&self.name, self.args.iter().enumerate().map(|(i, arg)| f(i, arg)).collect()).into()
+    }
+
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let unevaluated = WrappedReal::Expression(self.clone().into()).into();
+
+        let Some(function) = find_function(&self.name) else {
+            return Ok(unevaluated);
+        };
+
+        let Ok([argument]) = <[Types; 1]>::try_from(children) else {
+            return Err(Error::type_mismatch(format!("{} expects 1 argument, got {}", self.name, self.args.len())));
+        };
+
+        if let Some(result) = (function.exact)(&argument) {
+            return Ok(result);
+        }
+
+        // Only real arguments are evaluated numerically, exact ones stay symbolic
+        match argument {
+            Types::Real(Wrapper::Constant(real)) => Ok(Types::Real(Wrapper::Constant((function.evaluate)(real)?))),
+            _ => Ok(unevaluated),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use crate::{Error, Expressions, Types, TypeTrait, WrappedNatural, WrappedRational, WrappedReal, WrappedZahl};
+
+use super::{fmt_body, fmt_child, Addition, BinaryOperation, Exponentiation, Multiplication, Operation, OperationTrait, Precedence, Subtraction};
+
+/// Name of the arbitrary constant added to indefinite integrals, the brackets keep
+/// the parser from producing it so a variable C of the user is never mistaken for it
+pub const INTEGRATION_CONSTANT: &str = "[C]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Integral {
+    pub expression: Expressions,
+    pub variable: String,
+    // Lower and upper bound of a definite integral
+    pub bounds: Option<(Expressions, Expressions)>,
+}
+
+impl Integral {
+    pub fn new(expression: Expressions, variable: &str) -> Self {
+        Self { expression, variable: variable.to_string(), bounds: None }
+    }
+
+    pub fn new_definite(expression: Expressions, variable: &str, lower: Expressions, upper: Expressions) -> Self {
+        Self { expression, variable: variable.to_string(), bounds: Some((lower, upper)) }
+    }
+
+    /// Same integral over a different integrand
+    pub fn with_expression(&self, expression: Expressions) -> Self {
+        Self { expression, variable: self.variable.clone(), bounds: self.bounds.clone() }
+    }
+
+    fn variable(&self) -> Expressions {
+        WrappedReal::new_variable(&self.variable).into()
+    }
+
+    /// int c dx = c * x
+    pub fn integrate_constant(&self) -> Option<Expressions> {
+        if self.expression.contains_variable(&self.variable) {
+            return None;
+        }
+
+        Some(Multiplication::new(self.expression.clone(), self.variable()).into())
+    }
+
+    /// int x ** n dx = 1/(n + 1) * x ** (n + 1), for integer n != -1
+    pub fn integrate_power(&self) -> Option<Expressions> {
+        let (base, power) = match self.expression.is_exponentiation() {
+            Some(exp) => (exp.left, exp.right.is_natural().map(|n| n.get() as i64).or(exp.right.is_zahl().map(|z| z.get() as i64))?),
+            None => (self.expression.clone(), 1),
+        };
+
+        if base.get_variable()? != self.variable || power == -1 {
+            return None;
+        }
+
+        let exponent: Expressions = if power >= 0 {
+            WrappedNatural::new(u32::try_from(power).ok()?.checked_add(1)?).into()
+        } else {
+            WrappedZahl::new(power as i32 + 1).into()
+        };
+
+        Some(Multiplication::new(
+            WrappedRational::new(1, power + 1).into(),
+            Exponentiation::new(base, exponent).into(),
+        ).into())
+    }
+
+    /// F + C for the antiderivative F of an indefinite integral
+    pub fn with_constant(antiderivative: Expressions) -> Expressions {
+        Addition::new(antiderivative, WrappedReal::new_variable(INTEGRATION_CONSTANT).into()).into()
+    }
+
+    /// Antiderivative of the integrand by linearity, constant and power rule
+    pub fn antiderivative(&self) -> Option<Expressions> {
+        if let Some(result) = self.integrate_constant().or_else(|| self.integrate_power()) {
+            return Some(result);
+        }
+
+        if let Some(add) = self.expression.is_addition() {
+            return Some(Addition::new(
+                self.with_expression(add.left).antiderivative()?,
+                self.with_expression(add.right).antiderivative()?,
+            ).into());
+        }
+
+        if let Some(sub) = self.expression.is_subtraction() {
+            return Some(Subtraction::new(
+                self.with_expression(sub.left).antiderivative()?,
+                self.with_expression(sub.right).antiderivative()?,
+            ).into());
+        }
+
+        let mul = self.expression.is_multiplication()?;
+        if !mul.left.contains_variable(&self.variable) {
+            Some(Multiplication::new(mul.left, self.with_expression(mul.right).antiderivative()?).into())
+        } else if !mul.right.contains_variable(&self.variable) {
+            Some(Multiplication::new(mul.right, self.with_expression(mul.left).antiderivative()?).into())
+        } else {
+            None
+        }
+    }
+
+    /// F(b) - F(a) of a definite integral with antiderivative F
+    pub fn evaluate_bounds(&self) -> Option<Expressions> {
+        let (lower, upper) = self.bounds.as_ref()?;
+        let antiderivative = self.antiderivative()?;
+
+        Some(Subtraction::new(
+            antiderivative.substitute(&self.variable, upper),
+            antiderivative.substitute(&self.variable, lower),
+        ).into())
+    }
+}
+
+impl Display for Integral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "int")?;
+
+        if let Some((lower, upper)) = &self.bounds {
+            write!(f, "[")?;
+            fmt_child(f, lower)?;
+            write!(f, ", ")?;
+            fmt_child(f, upper)?;
+            write!(f, "]")?;
+        }
+
+        write!(f, " ")?;
+        fmt_body(f, &self.expression)?;
+        write!(f, " d{}", self.variable)
+    }
+}
+
+impl OperationTrait for Integral {
+    fn get_children(&self) -> Vec<Expressions> {
+        match &self.bounds {
+            Some((lower, upper)) => vec![self.expression.clone(), lower.clone(), upper.clone()],
+            None => vec![self.expression.clone()],
+        }
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Integral {
+            expression: f(0, &self.expression),
+            variable: self.variable.clone(),
+            bounds: self.bounds.as_ref().map(|(lower, upper)| (f(1, lower), f(2, upper))),
+        }.into()
+    }
+
+    fn evaluate(&self, _children: Vec<Types>) -> Result<Types, Error> {
+        // Definite integrals with constant bounds are evaluated exactly,
+        // everything else stays an unevaluated integral
+        if let Some(result) = self.evaluate_bounds() {
+            let result = result.solve()?;
+
+            if result.is_value() {
+                return Ok(result);
+            }
+        }
+
+        Ok(WrappedReal::Expression(self.clone().into()).into())
+    }
+
+    fn get_bound_variable(&self) -> Option<String> {
+        self.bounds.as_ref().map(|_| self.variable.clone())
+    }
+
+    fn set_bound_variable(&mut self, name: &str) {
+        self.variable = name.to_string();
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Binder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, rules::simplify, Expressions, Integral};
+
+    fn integral(input: &str) -> Integral {
+        Integral::new(parse(input).unwrap(), "x")
+    }
+
+    #[test]
+    fn power_rule() {
+        assert_eq!(integral("x ** 2").integrate_power().unwrap().to_string(), "1/3 * x ** 3");
+        assert_eq!(integral("x").integrate_power().unwrap().to_string(), "1/2 * x ** 2");
+        assert_eq!(integral("x ** (0 - 1)").integrate_power(), None);
+    }
+
+    #[test]
+    fn power_rule_overflow() {
+        assert_eq!(integral("x ** 4294967295").integrate_power(), None);
+    }
+
+    #[test]
+    fn indefinite_integrals_have_one_constant() {
+        let result = simplify(&Expressions::from(integral("4 * x + 5")));
+        assert_eq!(result.to_string(), "4 * (1/2 * x ** 2) + 5 * x + [C]");
+    }
+
+    #[test]
+    fn definite_integrals_are_evaluated() {
+        let integral = Integral::new_definite(parse("x ** 2").unwrap(), "x", parse("0").unwrap(), parse("3").unwrap());
+        assert_eq!(Expressions::from(integral).solve().unwrap().to_string(), "9");
+    }
+}
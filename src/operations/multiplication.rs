@@ -1,13 +1,13 @@
 use std::fmt::Display;
 
-use crate::{Expressions, Types, Wrapper};
+use crate::{dispatch, Error, Expressions, Types, Wrapper};
 
-use super::OperationTrait;
+use super::{binary, fmt_operand, Operation, OperationTrait, Precedence};
 
 pub trait Mul<Rhs = Self> {
     type Output;
 
-    fn mul(self, other: Rhs) -> Result<Self::Output, String>;
+    fn mul(self, other: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl<L, R, O> Mul<Wrapper<R>> for Wrapper<L> where
@@ -19,7 +19,7 @@ impl<L, R, O> Mul<Wrapper<R>> for Wrapper<L> where
 {
     type Output = Types;
 
-    fn mul(self, rhs: Wrapper<R>) -> Result<Self::Output, String> {
+    fn mul(self, rhs: Wrapper<R>) -> Result<Self::Output, Error> {
         match (self, rhs) {
             (Wrapper::<L>::Constant(lhs), Wrapper::<R>::Constant(rhs)) => Ok((lhs.mul(rhs))?.into().into()),
             (lhs, rhs) => Ok(Wrapper::<O>::Expression(Multiplication::new(lhs.into(), rhs.into()).into()).into()),
@@ -27,11 +27,7 @@ impl<L, R, O> Mul<Wrapper<R>> for Wrapper<L> where
     }
 }
 
-pub trait TypeMul {
-    fn type_mul(self, right: Types) -> Result<Types, String>;
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Multiplication {
     pub left: Expressions,
     pub right: Expressions,
@@ -45,11 +41,9 @@ impl Multiplication {
 
 impl Display for Multiplication {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            write!(f, "({:#} * {:#})", self.left, self.right)
-        } else {
-            write!(f, "({} * {})", self.left, self.right)
-        }
+        fmt_operand(f, &self.left, Precedence::Product)?;
+        write!(f, " * ")?;
+        fmt_operand(f, &self.right, Precedence::Power)
     }
 }
 
@@ -58,15 +52,16 @@ impl OperationTrait for Multiplication {
         vec![self.left.clone(), self.right.clone()]
     }
 
-    fn copy(&self) -> Expressions {
-        Multiplication::new(self.left.copy(), self.right.copy()).into()
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Multiplication::new(f(0, &self.left), f(1, &self.right)).into()
     }
 
-    fn solve(&self) -> Result<Types, String> {
-        match self.left.solve()? {
-            Types::Real(left) => Ok((left.type_mul(self.right.solve()?))?),
-            Types::Natural(left) => Ok((left.type_mul(self.right.solve()?))?),
-            Types::Zahl(left) => Ok((left.type_mul(self.right.solve()?))?),
-        }
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let [left, right] = binary(children);
+        dispatch!(left.common(right), |left, right| left.mul(right))
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Product
     }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::{Error, Expressions, Types, WrappedNatural, WrappedReal};
+
+use super::{fmt_body, fmt_child, solve_terms, Multiplication, Operation, OperationTrait, Precedence};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Product {
+    pub index: String,
+    pub from: Expressions,
+    pub to: Expressions,
+    pub body: Expressions,
+}
+
+impl Product {
+    pub fn new(index: &str, from: Expressions, to: Expressions, body: Expressions) -> Self {
+        Self { index: index.to_string(), from, to, body }
+    }
+
+    pub fn with_body(&self, body: Expressions) -> Self {
+        Self::new(&self.index, self.from.clone(), self.to.clone(), body)
+    }
+
+    pub fn with_bounds(&self, from: Expressions, to: Expressions) -> Self {
+        Self::new(&self.index, from, to, self.body.clone())
+    }
+
+    /// Body evaluated at the given index
+    pub fn term(&self, index: &Expressions) -> Expressions {
+        self.body.substitute(&self.index, index)
+    }
+}
+
+impl Display for Product {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "prod[{} = ", self.index)?;
+        fmt_child(f, &self.from)?;
+        write!(f, ", ")?;
+        fmt_child(f, &self.to)?;
+        write!(f, "] ")?;
+
+        fmt_body(f, &self.body)
+    }
+}
+
+impl OperationTrait for Product {
+    fn get_children(&self) -> Vec<Expressions> {
+        vec![self.body.clone(), self.from.clone(), self.to.clone()]
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        let body = f(0, &self.body);
+        Product::new(&self.index, f(1, &self.from), f(2, &self.to), body).into()
+    }
+
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        self.evaluate_with(children, Expressions::solve)
+    }
+
+    fn evaluate_with(&self, children: Vec<Types>, solve: fn(&Expressions) -> Result<Types, Error>) -> Result<Types, Error> {
+        let combine = |left, right| Multiplication::new(left, right).into();
+
+        match solve_terms(&self.index, &children[1], &children[2], &self.body, WrappedNatural::new(1).into(), combine, solve)? {
+            Some(result) => Ok(result),
+            None => Ok(WrappedReal::Expression(self.clone().into()).into()),
+        }
+    }
+
+    fn get_bound_variable(&self) -> Option<String> {
+        Some(self.index.clone())
+    }
+
+    fn set_bound_variable(&mut self, name: &str) {
+        self.index = name.to_string();
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Binder
+    }
+}
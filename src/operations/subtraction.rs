@@ -1,15 +1,14 @@
 use std::fmt::Display;
 
-use crate::WrappedZahl;
-use crate::{Expressions, Types, Wrapper, MyInto};
+use crate::{dispatch, Error, Expressions, Types, Wrapper};
 use super::BinaryOperation;
 
-use super::OperationTrait;
+use super::{binary, fmt_operand, Operation, OperationTrait, Precedence};
 
 pub trait Sub<Rhs = Self> where {
     type Output;
 
-    fn sub(self, other: Rhs) -> Result<Self::Output, String>;
+    fn sub(self, other: Rhs) -> Result<Self::Output, Error>;
 }
 
 impl<L, R, O> Sub<Wrapper<R>> for Wrapper<L> where
@@ -21,7 +20,7 @@ impl<L, R, O> Sub<Wrapper<R>> for Wrapper<L> where
 {
     type Output = Types;
 
-    fn sub(self, rhs: Wrapper<R>) -> Result<Self::Output, String> {
+    fn sub(self, rhs: Wrapper<R>) -> Result<Self::Output, Error> {
         match (self, rhs) {
             (Wrapper::<L>::Constant(lhs), Wrapper::<R>::Constant(rhs)) => Ok((lhs.sub(rhs))?.into().into()),
             (lhs, rhs) => Ok(Wrapper::<O>::Expression(Subtraction::new(lhs.into(), rhs.into()).into()).into()),
@@ -29,11 +28,7 @@ impl<L, R, O> Sub<Wrapper<R>> for Wrapper<L> where
     }
 }
 
-pub trait TypeSub {
-    fn type_sub(self, right: Types) -> Result<Types, String>;
-}
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Subtraction {
     pub left: Expressions,
     pub right: Expressions,
@@ -41,11 +36,9 @@ pub struct Subtraction {
 
 impl Display for Subtraction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if f.alternate() {
-            write!(f, "({:#} - {:#})", self.left, self.right)
-        } else {
-            write!(f, "({} - {})", self.left, self.right)
-        }
+        fmt_operand(f, &self.left, Precedence::Sum)?;
+        write!(f, " - ")?;
+        fmt_operand(f, &self.right, Precedence::Product)
     }
 }
 
@@ -54,16 +47,17 @@ impl OperationTrait for Subtraction {
         vec![self.left.clone(), self.right.clone()]
     }
 
-    fn copy(&self) -> Expressions {
-        Subtraction::new(self.left.copy(), self.right.copy()).into()
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        Subtraction::new(f(0, &self.left), f(1, &self.right)).into()
     }
 
-    fn solve(&self) -> Result<Types, String> {
-        match self.left.solve()? {
-            Types::Natural(left) => Ok((MyInto::<WrappedZahl>::my_into(left).type_sub(self.right.solve()?))?),
-            Types::Zahl(left) => Ok((left.type_sub(self.right.solve()?))?),
-            Types::Real(left) => Ok((left.type_sub(self.right.solve()?))?),
-        }
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        let [left, right] = binary(children);
+        dispatch!(left.common(right), |left, right| left.sub(right))
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Sum
     }
 }
 
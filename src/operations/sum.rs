@@ -0,0 +1,200 @@
+use std::fmt::Display;
+
+use crate::{Error, Expressions, Types, TypeTrait, WrappedNatural, WrappedRational, WrappedReal, WrappedZahl};
+
+use super::{fmt_body, fmt_child, Addition, BinaryOperation, Operation, OperationTrait, Precedence};
+
+// Largest range of a sum or product that is expanded when solving
+const MAX_TERMS: u64 = 10_000;
+
+// Index value as the lowest exact type that holds it
+fn index_value(index: i64) -> Expressions {
+    if let Ok(natural) = u32::try_from(index) {
+        WrappedNatural::new(natural).into()
+    } else if let Ok(zahl) = i32::try_from(index) {
+        WrappedZahl::new(zahl).into()
+    } else {
+        WrappedRational::new(index, 1).into()
+    }
+}
+
+// Solves every term of a sum or product with constant integer bounds,
+// None if the bounds or any term are not constant
+pub(super) fn solve_terms(
+    index: &str,
+    from: &Types,
+    to: &Types,
+    body: &Expressions,
+    empty: Types,
+    combine: impl Fn(Expressions, Expressions) -> Expressions,
+    solve: fn(&Expressions) -> Result<Types, Error>,
+) -> Result<Option<Types>, Error> {
+    let (Some(from), Some(to)) = (from.to_integer(), to.to_integer()) else {
+        return Ok(None);
+    };
+
+    if to >= from && to.abs_diff(from) >= MAX_TERMS {
+        return Ok(None);
+    }
+
+    let mut result = empty;
+
+    for k in from..=to {
+        let term = solve(&body.substitute(index, &index_value(k))).map_err(|error| error.in_child(0))?;
+
+        if !term.is_value() {
+            return Ok(None);
+        }
+
+        result = combine(result.into(), term.into()).solve()?;
+    }
+
+    Ok(Some(result))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sum {
+    pub index: String,
+    pub from: Expressions,
+    pub to: Expressions,
+    pub body: Expressions,
+}
+
+impl Sum {
+    pub fn new(index: &str, from: Expressions, to: Expressions, body: Expressions) -> Self {
+        Self { index: index.to_string(), from, to, body }
+    }
+
+    pub fn with_body(&self, body: Expressions) -> Self {
+        Self::new(&self.index, self.from.clone(), self.to.clone(), body)
+    }
+
+    pub fn with_bounds(&self, from: Expressions, to: Expressions) -> Self {
+        Self::new(&self.index, from, to, self.body.clone())
+    }
+
+    /// Body evaluated at the given index
+    pub fn term(&self, index: &Expressions) -> Expressions {
+        self.body.substitute(&self.index, index)
+    }
+}
+
+impl Display for Sum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sum[{} = ", self.index)?;
+        fmt_child(f, &self.from)?;
+        write!(f, ", ")?;
+        fmt_child(f, &self.to)?;
+        write!(f, "] ")?;
+
+        fmt_body(f, &self.body)
+    }
+}
+
+impl OperationTrait for Sum {
+    fn get_children(&self) -> Vec<Expressions> {
+        vec![self.body.clone(), self.from.clone(), self.to.clone()]
+    }
+
+    fn map_children(&self, f: &mut dyn FnMut(usize, &Expressions) -> Expressions) -> Operation {
+        let body = f(0, &self.body);
+        Sum::new(&self.index, f(1, &self.from), f(2, &self.to), body).into()
+    }
+
+    fn evaluate(&self, children: Vec<Types>) -> Result<Types, Error> {
+        self.evaluate_with(children, Expressions::solve)
+    }
+
+    fn evaluate_with(&self, children: Vec<Types>, solve: fn(&Expressions) -> Result<Types, Error>) -> Result<Types, Error> {
+        let combine = |left, right| Addition::new(left, right).into();
+
+        match solve_terms(&self.index, &children[1], &children[2], &self.body, WrappedNatural::new(0).into(), combine, solve)? {
+            Some(result) => Ok(result),
+            None => Ok(WrappedReal::Expression(self.clone().into()).into()),
+        }
+    }
+
+    fn get_bound_variable(&self) -> Option<String> {
+        Some(self.index.clone())
+    }
+
+    fn set_bound_variable(&mut self, name: &str) {
+        self.index = name.to_string();
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::Binder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, Expressions, WrappedReal};
+
+    fn evaluated(input: &str) -> String {
+        parse(input).unwrap().solve().unwrap().to_string()
+    }
+
+    #[test]
+    fn constant_bounds_are_expanded() {
+        assert_eq!(evaluated("sum(k, 1, 4, k ** 2)"), "30");
+        assert_eq!(evaluated("prod(k, 1, 5, k)"), "120");
+        assert_eq!(evaluated("sum(k, 1, 3, 1/4 * k)"), "3/2");
+    }
+
+    #[test]
+    fn numeric_evaluation_reaches_the_terms() {
+        let numeric = |input: &str| parse(input).unwrap().numeric().unwrap().to_string();
+
+        assert_eq!(numeric("sum(k, 1, 3, sqrt(k))"), (1.0 + 2f64.sqrt() + 3f64.sqrt()).to_string());
+        assert_eq!(numeric("prod(k, 1, 2, pi * k)"), (std::f64::consts::PI * (std::f64::consts::PI * 2.0)).to_string());
+        assert_eq!(evaluated("sum(k, 1, 3, sqrt(k))"), "sum[k = 1, 3] sqrt(k)");
+    }
+
+    #[test]
+    fn empty_ranges() {
+        assert_eq!(evaluated("sum(k, 3, 2, k)"), "0");
+        assert_eq!(evaluated("prod(k, 3, 2, k)"), "1");
+        assert_eq!(evaluated("sum(i, 1, 0 - 3, i)"), "0");
+    }
+
+    #[test]
+    fn integer_bounds() {
+        assert_eq!(evaluated("sum(k, 0 - 2, 1, k)"), "-2");
+        assert_eq!(evaluated("prod(k, 0 - 3, 0 - 1, k)"), "-6");
+        assert_eq!(evaluated("sum(k, 4/2, 3, k)"), "5");
+    }
+
+    #[test]
+    fn symbolic_bounds_stay_unevaluated() {
+        assert_eq!(evaluated("sum(k, 1, n, k)"), "sum[k = 1, n] k");
+        assert_eq!(evaluated("sum(k, 1, 3, k * x)"), "sum[k = 1, 3] k * x");
+    }
+
+    #[test]
+    fn substitution_skips_the_bound_index() {
+        let sum = parse("sum(k, 1, k, k * x)").unwrap();
+        let value: Expressions = WrappedReal::new_variable("y").into();
+
+        assert_eq!(sum.substitute("k", &value).to_string(), "sum[k = 1, y] k * x");
+        assert_eq!(sum.substitute("x", &value).to_string(), "sum[k = 1, k] k * y");
+    }
+
+    #[test]
+    fn substitution_renames_captured_indices() {
+        let sum = parse("sum(k, 1, 3, k * x)").unwrap();
+        let value: Expressions = WrappedReal::new_variable("k").into();
+
+        assert_eq!(sum.substitute("x", &value).to_string(), "sum[k1 = 1, 3] k1 * k");
+    }
+
+    #[test]
+    fn simultaneous_substitution() {
+        let swapped = parse("x - y").unwrap().substitute_all(&[
+            ("x".to_string(), parse("y").unwrap()),
+            ("y".to_string(), parse("x").unwrap()),
+        ]);
+
+        assert_eq!(swapped.to_string(), "y - x");
+    }
+}
@@ -0,0 +1,356 @@
+//! Parser of the textual expression syntax.
+
+use std::{iter::Peekable, str::Chars};
+
+use crate::{operations::{Addition, BinaryOperation, Division, Exponentiation, FunctionCall, Multiplication, Product, Subtraction, Sum}, visit::{walk_mut, VisitorMut}, Complex, Error, Expressions, InnerExpressions, Types, Wrapper, WrappedNatural, WrappedComplex, WrappedRational, WrappedReal, WrappedZahl};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Power,
+    Slash,
+    Comma,
+    LeftParen,
+    RightParen,
+}
+
+fn read_while(chars: &mut Peekable<Chars>, predicate: impl Fn(char) -> bool) -> String {
+    let mut result = String::new();
+
+    while let Some(&c) = chars.peek() {
+        if !predicate(c) {
+            break;
+        }
+
+        result.push(c);
+        chars.next();
+    }
+
+    result
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                chars.next();
+                continue;
+            },
+            '0'..='9' | '.' => Token::Number(read_while(&mut chars, |c| c.is_ascii_digit() || c == '.')),
+            'a'..='z' | 'A'..='Z' | '_' => Token::Identifier(read_while(&mut chars, |c| c.is_alphanumeric() || c == '_')),
+            '*' => {
+                chars.next();
+
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    tokens.push(Token::Power);
+                } else {
+                    tokens.push(Token::Star);
+                }
+                continue;
+            },
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            ',' => Token::Comma,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            _ => return Err(Error::parse(format!("Unexpected character: {c}"))),
+        };
+
+        if !matches!(token, Token::Number(_) | Token::Identifier(_)) {
+            chars.next();
+        }
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+// Turns the imaginary unit back into the variable i
+struct ImaginaryToIndex;
+
+impl VisitorMut for ImaginaryToIndex {
+    fn visit_mut(&mut self, expression: &mut Expressions) {
+        if expression.is_imaginary_unit() {
+            *expression = WrappedReal::new_variable("i").into();
+        } else {
+            walk_mut(self, expression);
+        }
+    }
+}
+
+// sum(i, from, to, body) and prod(i, from, to, body)
+fn series(name: &str, args: Vec<Expressions>) -> Result<Expressions, Error> {
+    let [index, from, to, body] = <[Expressions; 4]>::try_from(args)
+        .map_err(|_| Error::parse(format!("{name} expects an index, two bounds and a body")))?;
+
+    // i is the imaginary unit everywhere except as the index of a series and inside its body
+    let (index, body) = if index.is_imaginary_unit() {
+        let mut body = body;
+        ImaginaryToIndex.visit_mut(&mut body);
+        ("i".to_string(), body)
+    } else {
+        (index.get_variable().ok_or_else(|| Error::parse(format!("Invalid index: {index}")))?, body)
+    };
+
+    if name == "sum" {
+        Ok(Sum::new(&index, from, to, body).into())
+    } else {
+        Ok(Product::new(&index, from, to, body).into())
+    }
+}
+
+// Quotients of integer literals are fraction literals, all others are divisions
+fn quotient(left: Expressions, right: Expressions) -> Result<Expressions, Error> {
+    let numerator = match left.to_inner() {
+        InnerExpressions::Type(Types::Natural(Wrapper::Constant(natural))) => Some(natural.get() as i64),
+        InnerExpressions::Type(Types::Zahl(Wrapper::Constant(zahl))) => Some(zahl.get() as i64),
+        _ => None,
+    };
+
+    match (numerator, right.to_inner()) {
+        (Some(_), InnerExpressions::Type(Types::Natural(Wrapper::Constant(denominator)))) if denominator.get() == 0 => {
+            Err(Error::division_by_zero())
+        },
+        (Some(numerator), InnerExpressions::Type(Types::Natural(Wrapper::Constant(denominator)))) => {
+            Ok(WrappedRational::new(numerator, denominator.get() as i64).into())
+        },
+        _ => Ok(Division::new(left, right).into()),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), Error> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(Error::parse(format!("Expected {expected:?}, found {token:?}"))),
+            None => Err(Error::parse(format!("Expected {expected:?}, found end of input"))),
+        }
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Expressions, Error> {
+        let mut left = self.term()?;
+
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.next();
+                    left = Addition::new(left, self.term()?).into();
+                },
+                Some(Token::Minus) => {
+                    self.next();
+                    left = Subtraction::new(left, self.term()?).into();
+                },
+                _ => return Ok(left),
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Expressions, Error> {
+        let mut left = self.unary()?;
+
+        loop {
+            match self.peek() {
+                Some(Token::Star) => {
+                    self.next();
+                    left = Multiplication::new(left, self.unary()?).into();
+                },
+                Some(Token::Slash) => {
+                    self.next();
+                    left = quotient(left, self.unary()?)?;
+                },
+                _ => return Ok(left),
+            }
+        }
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Expressions, Error> {
+        if self.peek() != Some(&Token::Minus) {
+            return self.power();
+        }
+
+        self.next();
+        let operand = self.unary()?;
+
+        // Negative literals are constants, everything else is multiplied by -1
+        let negated: Option<Expressions> = match operand.to_inner() {
+            InnerExpressions::Type(Types::Natural(Wrapper::Constant(natural))) => {
+                i32::try_from(natural.get()).ok().map(|natural| WrappedZahl::new(-natural).into())
+            },
+            InnerExpressions::Type(Types::Rational(Wrapper::Constant(rational))) => Some(WrappedRational::new(-rational.numerator(), rational.denominator()).into()),
+            InnerExpressions::Type(Types::Real(Wrapper::Constant(real))) => Some(WrappedReal::new(-real.get()).into()),
+            _ => None,
+        };
+
+        Ok(negated.unwrap_or_else(|| Multiplication::new(WrappedZahl::new(-1).into(), operand).into()))
+    }
+
+    // power := primary ('**' unary)?
+    fn power(&mut self) -> Result<Expressions, Error> {
+        let base = self.primary()?;
+
+        if self.peek() == Some(&Token::Power) {
+            self.next();
+            return Ok(Exponentiation::new(base, self.unary()?).into());
+        }
+
+        Ok(base)
+    }
+
+    // primary := number | identifier ('(' arguments ')')? | '(' expression ')'
+    // where sum(...) and prod(...) are parsed as series and i is the imaginary unit
+    fn primary(&mut self) -> Result<Expressions, Error> {
+        match self.next() {
+            Some(Token::Number(number)) => {
+                if let Ok(natural) = number.parse::<u32>() {
+                    Ok(WrappedNatural::new(natural).into())
+                } else {
+                    number.parse::<f64>()
+                        .map(|real| WrappedReal::new(real).into())
+                        .map_err(|_| Error::parse(format!("Invalid number: {number}")))
+                }
+            },
+            Some(Token::Identifier(name)) => {
+                if self.peek() != Some(&Token::LeftParen) {
+                    return match name.as_str() {
+                        "i" => Ok(WrappedComplex::new(Complex::unit()).into()),
+                        _ => Ok(WrappedReal::new_variable(&name).into()),
+                    };
+                }
+
+                self.next();
+                let mut args = vec![self.expression()?];
+
+                while self.peek() == Some(&Token::Comma) {
+                    self.next();
+                    args.push(self.expression()?);
+                }
+
+                self.expect(Token::RightParen)?;
+
+                match name.as_str() {
+                    "sum" | "prod" => series(&name, args),
+                    _ => Ok(FunctionCall::new(&name, args).into()),
+                }
+            },
+            Some(Token::LeftParen) => {
+                let expression = self.expression()?;
+                self.expect(Token::RightParen)?;
+                Ok(expression)
+            },
+            Some(token) => Err(Error::parse(format!("Unexpected token: {token:?}"))),
+            None => Err(Error::parse("Unexpected end of input")),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Expressions, Error> {
+    let mut parser = Parser { tokens: tokenize(input)?, position: 0 };
+    let expression = parser.expression()?;
+
+    match parser.peek() {
+        None => Ok(expression),
+        Some(token) => Err(Error::parse(format!("Unexpected token: {token:?}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(input: &str) -> String {
+        parse(input).unwrap().to_string()
+    }
+
+    fn error(input: &str) -> String {
+        parse(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(parsed("1 + 2 * 3"), "1 + 2 * 3");
+        assert_eq!(parsed("(1 + 2) * 3"), "(1 + 2) * 3");
+        assert_eq!(parsed("a - (b - c)"), "a - (b - c)");
+        assert_eq!(parsed("2 ** 3 ** 2"), "2 ** 3 ** 2");
+        assert_eq!(parse("2 ** 3 ** 2").unwrap().solve().unwrap().to_string(), "512");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse("3").unwrap().solve().unwrap().get_type(), "Natural");
+        assert_eq!(parse("-3").unwrap().solve().unwrap().get_type(), "Zahl");
+        assert_eq!(parse("1/2").unwrap().solve().unwrap().get_type(), "Rational");
+        assert_eq!(parse("0.5").unwrap().solve().unwrap().get_type(), "Real");
+        assert_eq!(parsed("-1/2"), "-1/2");
+    }
+
+    #[test]
+    fn division() {
+        assert_eq!(parsed("x / 2"), "x / 2");
+        assert_eq!(parsed("a / b / c"), "a / b / c");
+        assert_eq!(parsed("a / (b * c)"), "a / (b * c)");
+        assert_eq!(parsed("(a + b) ** 2 / 3"), "(a + b) ** 2 / 3");
+        assert_eq!(parsed("2 ** 3 / 4"), "2 ** 3 / 4");
+        assert_eq!(parse("2 ** 3 / 4").unwrap().solve().unwrap().to_string(), "2");
+        assert_eq!(parsed("2 ** (3/4)"), "2 ** (3/4)");
+    }
+
+    #[test]
+    fn negation() {
+        assert_eq!(parsed("-x"), "(-1) * x");
+        assert_eq!(parsed("--3"), "(-1) * (-3)");
+        assert_eq!(parsed("-3000000000"), "(-1) * 3000000000");
+        assert_eq!(parsed("2 ** -1"), "2 ** (-1)");
+    }
+
+    #[test]
+    fn function_calls_and_series() {
+        assert_eq!(parsed("f(x, y + 1)"), "f(x, y + 1)");
+        assert_eq!(parsed("sum(k, 1, n, k ** 2)"), "sum[k = 1, n] k ** 2");
+    }
+
+    #[test]
+    fn i_is_the_imaginary_unit_except_as_an_index() {
+        assert!(parse("i").unwrap().is_imaginary_unit());
+        assert_eq!(parse("sum(i, 1, 3, i)").unwrap().solve().unwrap().to_string(), "6");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(""), "Unexpected end of input");
+        assert_eq!(error("(1 + 2"), "Expected RightParen, found end of input");
+        assert_eq!(error("1 + 2)"), "Unexpected token: RightParen");
+        assert_eq!(error("1 $ 2"), "Unexpected character: $");
+        assert_eq!(error("1.2.3"), "Invalid number: 1.2.3");
+        assert_eq!(error("1/0"), "Division by zero");
+        assert_eq!(error("sum(1, 2, 3, 4)"), "Invalid index: 1");
+        assert_eq!(error("sum(k, 1, 2)"), "sum expects an index, two bounds and a body");
+    }
+}
@@ -0,0 +1,273 @@
+//! Two dimensional text rendering of expressions.
+
+use std::fmt::Display;
+
+use crate::{operations::{Operation, Precedence}, Expressions, InnerExpressions, Types, Wrapper};
+
+/// Rectangular block of text, aligned with other blocks at its baseline
+#[derive(Debug, Clone)]
+pub struct Block {
+    // All lines have the same width
+    lines: Vec<String>,
+    baseline: usize,
+}
+
+fn width(line: &str) -> usize {
+    line.chars().count()
+}
+
+fn center(line: &str, total: usize) -> String {
+    let left = (total - width(line)) / 2;
+    let right = total - width(line) - left;
+    format!("{}{}{}", " ".repeat(left), line, " ".repeat(right))
+}
+
+impl Block {
+    fn text(text: &str) -> Self {
+        Self { lines: vec![text.to_string()], baseline: 0 }
+    }
+
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    fn width(&self) -> usize {
+        self.lines.first().map_or(0, |line| width(line))
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    // Blocks next to each other, aligned at their baselines
+    fn row(blocks: &[Block]) -> Self {
+        let above = blocks.iter().map(|block| block.baseline).max().unwrap_or(0);
+        let below = blocks.iter().map(|block| block.height() - block.baseline - 1).max().unwrap_or(0);
+        let mut lines = vec![String::new(); above + below + 1];
+
+        for block in blocks {
+            let offset = above - block.baseline;
+
+            for (i, line) in lines.iter_mut().enumerate() {
+                if i >= offset && i < offset + block.height() {
+                    line.push_str(&block.lines[i - offset]);
+                } else {
+                    line.push_str(&" ".repeat(block.width()));
+                }
+            }
+        }
+
+        Self { lines, baseline: above }
+    }
+
+    // Blocks centered above each other, with the baseline of the middle one
+    fn column(top: Option<Block>, middle: Block, bottom: Option<Block>) -> Self {
+        let blocks: Vec<&Block> = top.iter().chain(Some(&middle)).chain(bottom.iter()).collect();
+        let total = blocks.iter().map(|block| block.width()).max().unwrap_or(0);
+
+        let baseline = top.as_ref().map_or(0, |top| top.height()) + middle.baseline;
+        let lines = blocks.iter().flat_map(|block| block.lines.iter().map(|line| center(line, total))).collect();
+
+        Self { lines, baseline }
+    }
+
+    // Numerator and denominator separated by a bar on the baseline
+    fn fraction(numerator: Block, denominator: Block) -> Self {
+        let bar = Block::text(&"─".repeat(numerator.width().max(denominator.width())));
+        Self::column(Some(numerator), bar, Some(denominator))
+    }
+
+    // Exponent raised above the end of the base
+    fn superscript(base: Block, exponent: Block) -> Self {
+        let mut lines: Vec<String> = exponent.lines.iter()
+            .map(|line| format!("{}{}", " ".repeat(base.width()), line))
+            .collect();
+
+        lines.extend(base.lines.iter().map(|line| format!("{}{}", line, " ".repeat(exponent.width()))));
+
+        Self { lines, baseline: exponent.height() + base.baseline }
+    }
+
+    fn parenthesize(self) -> Self {
+        if self.height() == 1 {
+            return Self::row(&[Block::text("("), self, Block::text(")")]);
+        }
+
+        let side = |top: &str, middle: &str, bottom: &str| {
+            let mut lines = vec![top.to_string()];
+            lines.extend((2..self.height()).map(|_| middle.to_string()));
+            lines.push(bottom.to_string());
+            Block { lines, baseline: self.baseline }
+        };
+
+        let left = side("⎛", "⎜", "⎝");
+        let right = side("⎞", "⎟", "⎠");
+
+        Self::row(&[left, self, right])
+    }
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<&str> = self.lines.iter().map(|line| line.trim_end()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// Superscript digits of an integer constant, like ² or ⁻¹
+fn superscript_digits(expression: &Expressions) -> Option<String> {
+    let value = match expression.is_natural() {
+        Some(natural) => natural.get() as i64,
+        None => expression.is_zahl()?.get() as i64,
+    };
+
+    Some(value.to_string().chars().map(|c| match c {
+        '-' => '⁻',
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        _ => '⁹',
+    }).collect())
+}
+
+pub fn render(expression: &Expressions) -> Block {
+    match expression.to_inner() {
+        InnerExpressions::Type(types) => render_type(&types),
+        InnerExpressions::Operation(operation) => render_operation(&operation),
+    }
+}
+
+// Parenthesizes the operand if it binds weaker than required
+fn operand(expression: &Expressions, required: Precedence) -> Block {
+    if expression.precedence() < required {
+        render(expression).parenthesize()
+    } else {
+        render(expression)
+    }
+}
+
+// Body of a binder, which extends to the right anyway
+fn body(expression: &Expressions) -> Block {
+    if expression.precedence() == Precedence::Binder {
+        render(expression)
+    } else {
+        operand(expression, Precedence::Product)
+    }
+}
+
+fn render_type(types: &Types) -> Block {
+    match types {
+        Types::Rational(Wrapper::Constant(rational)) if rational.denominator() != 1 => {
+            let fraction = Block::fraction(
+                Block::text(&rational.numerator().abs().to_string()),
+                Block::text(&rational.denominator().to_string()),
+            );
+
+            if rational.numerator() < 0 {
+                Block::row(&[Block::text("−"), fraction])
+            } else {
+                fraction
+            }
+        },
+        _ => Block::text(&types.to_string().replace('-', "−")),
+    }
+}
+
+fn render_operation(operation: &Operation) -> Block {
+    match operation {
+        Operation::Addition(add) => Block::row(&[operand(&add.left, Precedence::Sum), Block::text(" + "), operand(&add.right, Precedence::Product)]),
+        Operation::Subtraction(sub) => Block::row(&[operand(&sub.left, Precedence::Sum), Block::text(" − "), operand(&sub.right, Precedence::Product)]),
+        Operation::Multiplication(mul) => Block::row(&[operand(&mul.left, Precedence::Product), Block::text(" · "), operand(&mul.right, Precedence::Power)]),
+        Operation::Division(div) => Block::fraction(render(&div.left), render(&div.right)),
+        Operation::Exponentiation(exp) => {
+            let base = operand(&exp.left, Precedence::Atom);
+
+            match superscript_digits(&exp.right) {
+                Some(digits) if base.height() == 1 => Block::row(&[base, Block::text(&digits)]),
+                _ => Block::superscript(base, render(&exp.right)),
+            }
+        },
+        Operation::Derivative(derivative) => Block::row(&[
+            Block::fraction(Block::text("d"), Block::text(&format!("d{}", derivative.variable))),
+            Block::text(" "),
+            body(&derivative.expression),
+        ]),
+        Operation::Integral(integral) => {
+            let symbol = match &integral.bounds {
+                Some((lower, upper)) => Block::column(Some(render(upper)), Block::text("∫"), Some(render(lower))),
+                None => Block::text("∫"),
+            };
+
+            Block::row(&[symbol, Block::text(" "), body(&integral.expression), Block::text(&format!(" d{}", integral.variable))])
+        },
+        Operation::FunctionCall(call) => {
+            let mut args = vec![];
+
+            for (i, arg) in call.args.iter().enumerate() {
+                if i > 0 {
+                    args.push(Block::text(", "));
+                }
+
+                args.push(render(arg));
+            }
+
+            let name = match call.name.as_str() {
+                "sqrt" => "√",
+                name => name,
+            };
+
+            Block::row(&[Block::text(name), Block::row(&args).parenthesize()])
+        },
+        Operation::Sum(sum) => Block::row(&[
+            Block::column(
+                Some(render(&sum.to)),
+                Block::text("Σ"),
+                Some(Block::row(&[Block::text(&format!("{}=", sum.index)), render(&sum.from)])),
+            ),
+            Block::text(" "),
+            body(&sum.body),
+        ]),
+        Operation::Product(product) => Block::row(&[
+            Block::column(
+                Some(render(&product.to)),
+                Block::text("Π"),
+                Some(Block::row(&[Block::text(&format!("{}=", product.index)), render(&product.from)])),
+            ),
+            Block::text(" "),
+            body(&product.body),
+        ]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    fn rendered(input: &str) -> String {
+        render(&parse(input).unwrap()).to_string()
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(rendered("a + b * c"), "a + b · c");
+        assert_eq!(rendered("x ** 2"), "x²");
+    }
+
+    #[test]
+    fn fractions_are_stacked() {
+        assert_eq!(rendered("1/2"), "1\n─\n2");
+        assert_eq!(render(&parse("x + 1/2").unwrap()).baseline(), 1);
+    }
+
+    #[test]
+    fn exponents_are_raised() {
+        assert_eq!(rendered("x ** (n + 1)"), " n + 1\nx");
+    }
+}
@@ -1,9 +1,29 @@
-use crate::{operations::{Exponentiation, Subtraction}, Addition, Expressions, InnerExpressions, Multiplication, Rule, WrappedNatural, TypeTrait};
+//! Rewrite rules and the search for rules applicable to an expression.
 
-use super::operations::OperationTrait;
+use crate::{definitions::Definition, operations::{Exponentiation, Subtraction}, Addition, Expressions, Multiplication, Rule, WrappedNatural, TypeTrait};
+
+use super::visit::{walk_mut, VisitorMut};
+
+mod derivative;
+use derivative::*;
+
+mod integral;
+use integral::*;
+
+mod function;
+use function::*;
+
+mod series;
+use series::*;
+
+mod complex;
+use complex::*;
+
+mod surd;
+use surd::*;
 
 static DISTRIBUTIVITY: Rule = Rule {
-    matches: &|expression: &Expressions| {
+    matches: |expression: &Expressions| {
         let mul = expression.is_multiplication()?;
         let add = mul.right.is_addition()?;
 
@@ -17,7 +37,7 @@ static DISTRIBUTIVITY: Rule = Rule {
 };
 
 static EXPONENT_TO_MULTIPLICATION: Rule = Rule {
-    matches: &|expression: &Expressions| {
+    matches: |expression: &Expressions| {
         let exp = expression.is_exponentiation()?;
 
         Some(Multiplication {
@@ -36,12 +56,12 @@ static EXPONENT_TO_MULTIPLICATION: Rule = Rule {
 };
 
 static EXPONENT_IDENTITY: Rule = Rule {
-    matches: &|expression: &Expressions| {
+    matches: |expression: &Expressions| {
         let exp = expression.is_exponentiation()?;
         let power = exp.right.is_zahl()?;
 
         if power.get() == 1 {
-            Some(exp.left.copy().to_inner())
+            Some(exp.left.clone().to_inner())
         } else {
             None
         }
@@ -50,9 +70,57 @@ static EXPONENT_IDENTITY: Rule = Rule {
     description: "a ** 1 = 1, a ** 1 = a"
 };
 
+static MULTIPLICATIVE_IDENTITY: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let mul = expression.is_multiplication()?;
+
+        if mul.left.is_one() {
+            Some(mul.right.to_inner())
+        } else if mul.right.is_one() {
+            Some(mul.left.to_inner())
+        } else {
+            None
+        }
+    },
+    name: "Multiplicative Identity",
+    description: "1 * x = x, x * 1 = x"
+};
+
+static MULTIPLICATIVE_ANNIHILATION: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let mul = expression.is_multiplication()?;
+
+        if mul.left.is_zero() || mul.right.is_zero() {
+            Some(WrappedNatural::new(0).into())
+        } else {
+            None
+        }
+    },
+    name: "Multiplicative Annihilation",
+    description: "0 * x = 0, x * 0 = 0"
+};
+
+static ADDITIVE_IDENTITY: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(add) = expression.is_addition() {
+            if add.left.is_zero() {
+                return Some(add.right.to_inner());
+            } else if add.right.is_zero() {
+                return Some(add.left.to_inner());
+            }
+        }
+
+        let sub = expression.is_subtraction()?;
+        if sub.right.is_zero() { Some(sub.left.to_inner()) } else { None }
+    },
+    name: "Additive Identity",
+    description: "0 + x = x, x + 0 = x, x - 0 = x"
+};
+
 static CONST_EVALUATION: Rule = Rule {
-    matches: &|expression: &Expressions| {
-        let result = expression.is_operation()?.solve().unwrap();
+    matches: |expression: &Expressions| {
+        expression.is_operation()?;
+        let result = expression.solve().ok()?;
 
         if result.is_value() {
             Some(result.clone().into())
@@ -64,42 +132,275 @@ static CONST_EVALUATION: Rule = Rule {
     description: "1 + 1 = 2"
 };
 
-static RULES: [&Rule; 4] = [
+static RULES: [&Rule; 33] = [
     &DISTRIBUTIVITY, 
     &CONST_EVALUATION,
     &EXPONENT_TO_MULTIPLICATION,
-    &EXPONENT_IDENTITY
+    &EXPONENT_IDENTITY,
+    &MULTIPLICATIVE_IDENTITY,
+    &MULTIPLICATIVE_ANNIHILATION,
+    &ADDITIVE_IDENTITY,
+    &DERIVATIVE_CONSTANT,
+    &DERIVATIVE_VARIABLE,
+    &DERIVATIVE_SUM,
+    &DERIVATIVE_PRODUCT,
+    &DERIVATIVE_POWER,
+    &DERIVATIVE_CHAIN,
+    &DERIVATIVE_FUNCTION,
+    &INTEGRAL_SUM,
+    &INTEGRAL_FACTOR,
+    &INTEGRAL_CONSTANT,
+    &INTEGRAL_POWER,
+    &INTEGRAL_BOUNDS,
+    &INTEGRATION_CONSTANTS,
+    &LOGARITHM_OF_EXPONENTIAL,
+    &PYTHAGOREAN_IDENTITY,
+    &SPLIT_FIRST,
+    &SPLIT_LAST,
+    &SERIES_LINEARITY,
+    &SUM_FACTOR,
+    &SERIES_CONSTANT,
+    &GAUSS_SUM,
+    &SQUARE_SUM,
+    &IMAGINARY_SQUARE,
+    &IMAGINARY_PRODUCT,
+    &SURD_EXTRACTION,
+    &ROOT_SQUARE,
+];
+
+// Rules that never grow the expression without bound, used for automatic simplification
+static SIMPLIFICATION_RULES: [&Rule; 28] = [
+    &CONST_EVALUATION,
+    &EXPONENT_IDENTITY,
+    &MULTIPLICATIVE_IDENTITY,
+    &MULTIPLICATIVE_ANNIHILATION,
+    &ADDITIVE_IDENTITY,
+    &DERIVATIVE_CONSTANT,
+    &DERIVATIVE_VARIABLE,
+    &DERIVATIVE_SUM,
+    &DERIVATIVE_PRODUCT,
+    &DERIVATIVE_POWER,
+    &DERIVATIVE_CHAIN,
+    &DERIVATIVE_FUNCTION,
+    &INTEGRAL_SUM,
+    &INTEGRAL_FACTOR,
+    &INTEGRAL_CONSTANT,
+    &INTEGRAL_POWER,
+    &INTEGRAL_BOUNDS,
+    &INTEGRATION_CONSTANTS,
+    &LOGARITHM_OF_EXPONENTIAL,
+    &PYTHAGOREAN_IDENTITY,
+    &SERIES_LINEARITY,
+    &SERIES_CONSTANT,
+    &GAUSS_SUM,
+    &SQUARE_SUM,
+    &IMAGINARY_SQUARE,
+    &IMAGINARY_PRODUCT,
+    &SURD_EXTRACTION,
+    &ROOT_SQUARE,
 ];
 
+// Upper bound on rule applications of a single simplification
+const MAX_REWRITES: usize = 1_000;
+
 pub struct Match {
-    pub rule: &'static Rule,
-    pub result: InnerExpressions
+    pub name: &'static str,
+    pub description: String,
+    pub result: Expressions
 }
 
 impl std::fmt::Display for Match {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}: {}", self.rule.name, self.rule.description, self.result)
+        write!(f, "{}: {}: {}", self.name, self.description, self.result)
     }
 }
 
 impl Match {
-    pub fn result(&self) -> InnerExpressions {
-        // TODO: Should not clone
+    /// The replacement, sharing its nodes with the match
+    pub fn result(&self) -> Expressions {
         self.result.clone()
     }
 }
 
-pub fn find_all_rules(expression: &Expressions) -> Vec<Match> {
+pub fn find_all_rules(expression: &Expressions, definitions: &[Definition]) -> Vec<Match> {
     let mut rules: Vec<Match> = vec![];
 
     for rule in RULES.iter() {
         if let Some(result) = (rule.matches)(expression) {
             rules.push(Match {
-                rule,
+                name: rule.name,
+                description: rule.description.to_string(),
+                result: Expressions::new(result)
+            });
+        }
+    }
+
+    for definition in definitions.iter() {
+        if let Some(result) = definition.unfold(expression) {
+            rules.push(Match {
+                name: "Unfold",
+                description: definition.to_string(),
+                result
+            });
+        }
+
+        if let Some(result) = definition.fold(expression) {
+            rules.push(Match {
+                name: "Fold",
+                description: definition.to_string(),
                 result
             });
         }
     }
 
     rules
-}
\ No newline at end of file
+}
+
+// Paths of all subexpressions in preorder
+fn subexpression_paths(expression: &Expressions, path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+    paths.push(path.clone());
+
+    for (i, child) in expression.get_children().iter().enumerate() {
+        path.push(i);
+        subexpression_paths(child, path, paths);
+        path.pop();
+    }
+}
+
+/// Maps the items on all available cores, keeping their order
+pub(crate) fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = std::thread::available_parallelism().map_or(1, |workers| workers.get());
+    let chunk_size = items.len().div_ceil(workers).max(1);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(&f).collect::<Vec<R>>()))
+            .collect();
+
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    })
+}
+
+/// Rules applicable anywhere in the expression with the path to their subexpression, searched in parallel
+pub fn find_rules_in_tree(expression: &Expressions, definitions: &[Definition]) -> Vec<(Vec<usize>, Match)> {
+    let mut paths = vec![];
+    subexpression_paths(expression, &mut vec![], &mut paths);
+
+    parallel_map(&paths, |path| {
+        let subexpression = expression.at(path).unwrap();
+        find_all_rules(&subexpression, definitions).into_iter().map(|found| (path.clone(), found)).collect::<Vec<_>>()
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Applies simplification rules bottom up until none matches
+pub fn simplify(expression: &Expressions) -> Expressions {
+    let mut expression = expression.clone();
+    Simplifier { budget: MAX_REWRITES }.visit_mut(&mut expression);
+    expression
+}
+
+// Rewrites children first, then the node itself until no rule matches or the budget is used up
+struct Simplifier {
+    budget: usize,
+}
+
+impl VisitorMut for Simplifier {
+    fn visit_mut(&mut self, expression: &mut Expressions) {
+        walk_mut(self, expression);
+
+        while self.budget > 0 {
+            let Some(result) = SIMPLIFICATION_RULES.iter().find_map(|rule| (rule.matches)(expression)) else {
+                break;
+            };
+
+            self.budget -= 1;
+            *expression = Expressions::new(result);
+            walk_mut(self, expression);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, state::State, Derivative, Expressions, Integral, Multiplication};
+    use super::*;
+
+    fn simplified(input: &str) -> String {
+        simplify(&parse(input).unwrap()).to_string()
+    }
+
+    fn derivative(input: &str) -> String {
+        simplify(&Derivative::new(parse(input).unwrap(), "x").into()).to_string()
+    }
+
+    #[test]
+    fn identities_are_removed() {
+        assert_eq!(simplified("0 * x + 1 * exp(x) - 0"), "exp(x)");
+        assert_eq!(simplified("x * 1 + y * 0"), "x");
+        assert_eq!(simplified("0 + x"), "x");
+    }
+
+    #[test]
+    fn derivatives_of_polynomials() {
+        assert_eq!(derivative("x ** 3"), "3 * x ** 2");
+        assert_eq!(derivative("5 * x"), "5");
+        assert_eq!(derivative("y"), "0");
+    }
+
+    #[test]
+    fn derivatives_of_functions() {
+        assert_eq!(derivative("exp(x) * 5"), "exp(x) * 5");
+        assert_eq!(derivative("sin(x)"), "cos(x)");
+    }
+
+    #[test]
+    fn only_constants_of_integration_are_merged() {
+        let matches = find_all_rules(&parse("2 * (x + C)").unwrap(), &[]);
+        assert!(matches.iter().all(|found| found.name != "Integration Constants"));
+        assert_eq!(simplified("(x + C) + (y + C)"), "x + C + (y + C)");
+
+        let integral = Integral::with_constant(parse("x").unwrap());
+        assert_eq!(simplify(&Multiplication::new(parse("2").unwrap(), integral).into()).to_string(), "2 * x + [C]");
+    }
+
+    #[test]
+    fn series_are_only_split_over_nonempty_integer_ranges() {
+        let splits = ["Gauss Sum", "Sum of Squares", "Series Constant", "Split First Term", "Split Last Term"];
+
+        for input in ["sum(i, 1, 0 - 3, i)", "sum(i, 1, 0 - 3, i ** 2)", "prod(i, 2, 1, 5)", "sum(i, 1, n, i)"] {
+            let matches = find_all_rules(&parse(input).unwrap(), &[]);
+            assert!(matches.iter().all(|found| !splits.contains(&found.name)), "{input}");
+        }
+
+        let matches = find_all_rules(&parse("sum(i, 1, 4, i)").unwrap(), &[]);
+        assert!(matches.iter().any(|found| found.name == "Gauss Sum"));
+        assert_eq!(simplified("sum(i, 1, 0 - 3, i)"), "0");
+    }
+
+    #[test]
+    fn matches_are_listed_with_their_rule() {
+        let expression: Expressions = parse("x * (a + b)").unwrap();
+        let matches = find_all_rules(&expression, &[]);
+
+        assert_eq!(matches[0].name, "Distributivity");
+        assert_eq!(matches[0].result.to_string(), "x * b + x * a");
+    }
+
+    #[test]
+    fn untouched_subtrees_are_shared_with_the_history() {
+        let mut state = State::new(parse("x * (a + b) + sin(y)").unwrap());
+        state.selection_path = vec![0];
+
+        let matches = find_all_rules(&state.selection(), &state.definitions);
+        state.apply(matches[0].result(), matches[0].name);
+
+        let before = state.history[0].equation.get_children();
+        let after = state.current.get_children();
+
+        assert_ne!(after[0], before[0]);
+        assert!(std::sync::Arc::ptr_eq(&after[1].0, &before[1].0));
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{Expressions, Rule, WrappedZahl};
+
+pub static IMAGINARY_SQUARE: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let exp = expression.is_exponentiation()?;
+
+        if exp.left.is_imaginary_unit() && exp.right.is_natural()?.get() == 2 {
+            Some(WrappedZahl::new(-1).into())
+        } else {
+            None
+        }
+    },
+    name: "Imaginary Square",
+    description: "i ** 2 = -1"
+};
+
+pub static IMAGINARY_PRODUCT: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let mul = expression.is_multiplication()?;
+
+        if mul.left.is_imaginary_unit() && mul.right.is_imaginary_unit() {
+            Some(WrappedZahl::new(-1).into())
+        } else {
+            None
+        }
+    },
+    name: "Imaginary Product",
+    description: "i * i = -1"
+};
@@ -0,0 +1,139 @@
+use crate::{functions::find_function, operations::{BinaryOperation, Derivative, Exponentiation, Subtraction}, Addition, Expressions, Multiplication, Rule, WrappedNatural};
+
+fn is_integer(expression: &Expressions) -> bool {
+    expression.is_natural().is_some() || expression.is_zahl().is_some()
+}
+
+pub static DERIVATIVE_CONSTANT: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+
+        if derivative.expression.contains_variable(&derivative.variable) {
+            None
+        } else {
+            Some(WrappedNatural::new(0).into())
+        }
+    },
+    name: "Constant Rule",
+    description: "d/dx c = 0"
+};
+
+pub static DERIVATIVE_VARIABLE: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+
+        if derivative.expression.get_variable()? == derivative.variable {
+            Some(WrappedNatural::new(1).into())
+        } else {
+            None
+        }
+    },
+    name: "Identity Rule",
+    description: "d/dx x = 1"
+};
+
+pub static DERIVATIVE_SUM: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+        let variable = &derivative.variable;
+
+        if let Some(add) = derivative.expression.is_addition() {
+            Some(Addition::new(
+                Derivative::new(add.left, variable).into(),
+                Derivative::new(add.right, variable).into(),
+            ).into())
+        } else {
+            let sub = derivative.expression.is_subtraction()?;
+
+            Some(Subtraction::new(
+                Derivative::new(sub.left, variable).into(),
+                Derivative::new(sub.right, variable).into(),
+            ).into())
+        }
+    },
+    name: "Sum Rule",
+    description: "d/dx (u + v) = d/dx u + d/dx v"
+};
+
+pub static DERIVATIVE_PRODUCT: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+        let variable = &derivative.variable;
+        let mul = derivative.expression.is_multiplication()?;
+
+        Some(Addition::new(
+            Multiplication::new(Derivative::new(mul.left.clone(), variable).into(), mul.right.clone()).into(),
+            Multiplication::new(mul.left, Derivative::new(mul.right, variable).into()).into(),
+        ).into())
+    },
+    name: "Product Rule",
+    description: "d/dx (u * v) = d/dx u * v + u * d/dx v"
+};
+
+pub static DERIVATIVE_POWER: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+        let exp = derivative.expression.is_exponentiation()?;
+
+        if exp.left.get_variable()? != derivative.variable || !is_integer(&exp.right) {
+            return None;
+        }
+
+        Some(Multiplication::new(
+            exp.right.clone(),
+            Exponentiation::new(
+                exp.left,
+                Subtraction::new(exp.right, WrappedNatural::new(1).into()).into(),
+            ).into(),
+        ).into())
+    },
+    name: "Power Rule",
+    description: "d/dx x ** n = n * x ** (n - 1)"
+};
+
+pub static DERIVATIVE_CHAIN: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+        let variable = &derivative.variable;
+        let exp = derivative.expression.is_exponentiation()?;
+
+        let is_variable = exp.left.get_variable().is_some_and(|name| &name == variable);
+        if is_variable || !exp.left.contains_variable(variable) || !is_integer(&exp.right) {
+            return None;
+        }
+
+        Some(Multiplication::new(
+            Multiplication::new(
+                exp.right.clone(),
+                Exponentiation::new(
+                    exp.left.clone(),
+                    Subtraction::new(exp.right, WrappedNatural::new(1).into()).into(),
+                ).into(),
+            ).into(),
+            Derivative::new(exp.left, variable).into(),
+        ).into())
+    },
+    name: "Chain Rule",
+    description: "d/dx u ** n = n * u ** (n - 1) * d/dx u"
+};
+
+pub static DERIVATIVE_FUNCTION: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let derivative = expression.is_derivative()?;
+        let variable = &derivative.variable;
+        let call = derivative.expression.is_function_call()?;
+        let function = find_function(&call.name)?;
+        let argument = call.argument_of(function.name)?;
+
+        if !argument.contains_variable(variable) {
+            return None;
+        }
+
+        Some(Multiplication::new(
+            (function.derivative)(&argument),
+            Derivative::new(argument, variable).into(),
+        ).into())
+    },
+    name: "Function Chain Rule",
+    description: "d/dx f(u) = f'(u) * d/dx u"
+};
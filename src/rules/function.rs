@@ -0,0 +1,44 @@
+use crate::{Expressions, Rule, WrappedNatural};
+
+// Argument of f(x) ** 2
+fn squared_argument(expression: &Expressions, name: &str) -> Option<Expressions> {
+    let exp = expression.is_exponentiation()?;
+
+    if exp.right.is_natural()?.get() != 2 {
+        return None;
+    }
+
+    exp.left.is_function_call()?.argument_of(name)
+}
+
+pub static LOGARITHM_OF_EXPONENTIAL: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let ln = expression.is_function_call()?.argument_of("ln")?;
+        let argument = ln.is_function_call()?.argument_of("exp")?;
+
+        Some(argument.to_inner())
+    },
+    name: "Logarithm of Exponential",
+    description: "ln(exp(x)) = x"
+};
+
+pub static PYTHAGOREAN_IDENTITY: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let add = expression.is_addition()?;
+
+        let matches = |sin: &Expressions, cos: &Expressions| {
+            match (squared_argument(sin, "sin"), squared_argument(cos, "cos")) {
+                (Some(sin), Some(cos)) => sin == cos,
+                _ => false,
+            }
+        };
+
+        if matches(&add.left, &add.right) || matches(&add.right, &add.left) {
+            Some(WrappedNatural::new(1).into())
+        } else {
+            None
+        }
+    },
+    name: "Pythagorean Identity",
+    description: "sin(x) ** 2 + cos(x) ** 2 = 1"
+};
@@ -0,0 +1,109 @@
+use crate::{operations::{BinaryOperation, Subtraction, INTEGRATION_CONSTANT}, Addition, Expressions, Integral, Multiplication, Rule};
+
+// F of F + C
+fn without_constant(expression: &Expressions) -> Option<Expressions> {
+    let add = expression.is_addition()?;
+
+    if add.right.get_variable()? == INTEGRATION_CONSTANT { Some(add.left) } else { None }
+}
+
+pub static INTEGRAL_SUM: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let integral = expression.is_integral()?;
+
+        if let Some(add) = integral.expression.is_addition() {
+            Some(Addition::new(
+                integral.with_expression(add.left).into(),
+                integral.with_expression(add.right).into(),
+            ).into())
+        } else {
+            let sub = integral.expression.is_subtraction()?;
+
+            Some(Subtraction::new(
+                integral.with_expression(sub.left).into(),
+                integral.with_expression(sub.right).into(),
+            ).into())
+        }
+    },
+    name: "Integral Linearity",
+    description: "int (u + v) dx = int u dx + int v dx"
+};
+
+pub static INTEGRAL_FACTOR: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let integral = expression.is_integral()?;
+        let mul = integral.expression.is_multiplication()?;
+
+        if !mul.left.contains_variable(&integral.variable) {
+            Some(Multiplication::new(mul.left, integral.with_expression(mul.right).into()).into())
+        } else if !mul.right.contains_variable(&integral.variable) {
+            Some(Multiplication::new(mul.right, integral.with_expression(mul.left).into()).into())
+        } else {
+            None
+        }
+    },
+    name: "Constant Factor",
+    description: "int c * u dx = c * int u dx"
+};
+
+pub static INTEGRAL_CONSTANT: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let integral = expression.is_integral()?;
+
+        if integral.bounds.is_some() {
+            return None;
+        }
+
+        Some(Integral::with_constant(integral.integrate_constant()?).to_inner())
+    },
+    name: "Integral Constant",
+    description: "int c dx = c * x + C"
+};
+
+pub static INTEGRAL_POWER: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let integral = expression.is_integral()?;
+
+        if integral.bounds.is_some() {
+            return None;
+        }
+
+        Some(Integral::with_constant(integral.integrate_power()?).to_inner())
+    },
+    name: "Integral Power Rule",
+    description: "int x ** n dx = 1/(n + 1) * x ** (n + 1) + C"
+};
+
+pub static INTEGRAL_BOUNDS: Rule = Rule {
+    matches: |expression: &Expressions| {
+        Some(expression.is_integral()?.evaluate_bounds()?.to_inner())
+    },
+    name: "Fundamental Theorem",
+    description: "int[a, b] f dx = F(b) - F(a)"
+};
+
+// Sums and multiples of arbitrary constants are again one arbitrary constant
+pub static INTEGRATION_CONSTANTS: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(add) = expression.is_addition() {
+            let sum = Addition::new(without_constant(&add.left)?, without_constant(&add.right)?);
+            return Some(Integral::with_constant(sum.into()).to_inner());
+        }
+
+        if let Some(sub) = expression.is_subtraction() {
+            let difference = Subtraction::new(without_constant(&sub.left)?, without_constant(&sub.right)?);
+            return Some(Integral::with_constant(difference.into()).to_inner());
+        }
+
+        let mul = expression.is_multiplication()?;
+
+        if mul.left.contains_variable(INTEGRATION_CONSTANT) {
+            return None;
+        }
+
+        let product = Multiplication::new(mul.left, without_constant(&mul.right)?);
+        Some(Integral::with_constant(product.into()).to_inner())
+    },
+    name: "Integration Constants",
+    description: "(F + C) + (G + C) = F + G + C, c * (F + C) = c * F + C"
+};
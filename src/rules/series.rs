@@ -0,0 +1,185 @@
+use crate::{operations::{BinaryOperation, Exponentiation, Subtraction}, Addition, Expressions, Multiplication, Rule, WrappedNatural, WrappedRational};
+
+fn successor(expression: &Expressions) -> Expressions {
+    Addition::new(expression.clone(), WrappedNatural::new(1).into()).into()
+}
+
+fn predecessor(expression: &Expressions) -> Expressions {
+    Subtraction::new(expression.clone(), WrappedNatural::new(1).into()).into()
+}
+
+// Number of terms from..=to
+fn count(from: &Expressions, to: &Expressions) -> Expressions {
+    successor(&Subtraction::new(to.clone(), from.clone()).into())
+}
+
+// Value of a bound that solves to an integer
+fn integer(bound: &Expressions) -> Option<i64> {
+    bound.solve().ok()?.to_integer()
+}
+
+// Whether the bounds are integers with at least one index between them.
+// Symbolic bounds could describe an empty range, for which splits and closed forms are wrong
+fn is_nonempty_range(from: &Expressions, to: &Expressions) -> bool {
+    matches!((integer(from), integer(to)), (Some(from), Some(to)) if from <= to)
+}
+
+// Whether the sum starts at 0 or 1 and is not empty, for which the closed forms are the same
+fn starts_at_zero_or_one(from: &Expressions, to: &Expressions) -> bool {
+    integer(from).is_some_and(|from| from == 0 || from == 1) && is_nonempty_range(from, to)
+}
+
+pub static SPLIT_FIRST: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(sum) = expression.is_sum() {
+            if !is_nonempty_range(&sum.from, &sum.to) {
+                return None;
+            }
+
+            Some(Addition::new(
+                sum.term(&sum.from),
+                sum.with_bounds(successor(&sum.from), sum.to.clone()).into(),
+            ).into())
+        } else {
+            let product = expression.is_product()?;
+
+            if !is_nonempty_range(&product.from, &product.to) {
+                return None;
+            }
+
+            Some(Multiplication::new(
+                product.term(&product.from),
+                product.with_bounds(successor(&product.from), product.to.clone()).into(),
+            ).into())
+        }
+    },
+    name: "Split First Term",
+    description: "sum[i = a, b] f = f(a) + sum[i = a + 1, b] f"
+};
+
+pub static SPLIT_LAST: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(sum) = expression.is_sum() {
+            if !is_nonempty_range(&sum.from, &sum.to) {
+                return None;
+            }
+
+            Some(Addition::new(
+                sum.with_bounds(sum.from.clone(), predecessor(&sum.to)).into(),
+                sum.term(&sum.to),
+            ).into())
+        } else {
+            let product = expression.is_product()?;
+
+            if !is_nonempty_range(&product.from, &product.to) {
+                return None;
+            }
+
+            Some(Multiplication::new(
+                product.with_bounds(product.from.clone(), predecessor(&product.to)).into(),
+                product.term(&product.to),
+            ).into())
+        }
+    },
+    name: "Split Last Term",
+    description: "sum[i = a, b] f = sum[i = a, b - 1] f + f(b)"
+};
+
+pub static SERIES_LINEARITY: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(sum) = expression.is_sum() {
+            if let Some(add) = sum.body.is_addition() {
+                Some(Addition::new(sum.with_body(add.left).into(), sum.with_body(add.right).into()).into())
+            } else {
+                let sub = sum.body.is_subtraction()?;
+                Some(Subtraction::new(sum.with_body(sub.left).into(), sum.with_body(sub.right).into()).into())
+            }
+        } else {
+            let product = expression.is_product()?;
+            let mul = product.body.is_multiplication()?;
+
+            Some(Multiplication::new(product.with_body(mul.left).into(), product.with_body(mul.right).into()).into())
+        }
+    },
+    name: "Series Linearity",
+    description: "sum[i = a, b] (u + v) = sum[i = a, b] u + sum[i = a, b] v"
+};
+
+pub static SUM_FACTOR: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let sum = expression.is_sum()?;
+        let mul = sum.body.is_multiplication()?;
+
+        if !mul.left.contains_variable(&sum.index) {
+            Some(Multiplication::new(mul.left, sum.with_body(mul.right).into()).into())
+        } else if !mul.right.contains_variable(&sum.index) {
+            Some(Multiplication::new(mul.right, sum.with_body(mul.left).into()).into())
+        } else {
+            None
+        }
+    },
+    name: "Sum Constant Factor",
+    description: "sum[i = a, b] c * f = c * sum[i = a, b] f"
+};
+
+pub static SERIES_CONSTANT: Rule = Rule {
+    matches: |expression: &Expressions| {
+        if let Some(sum) = expression.is_sum() {
+            if sum.body.contains_variable(&sum.index) || !is_nonempty_range(&sum.from, &sum.to) {
+                return None;
+            }
+
+            Some(Multiplication::new(count(&sum.from, &sum.to), sum.body).into())
+        } else {
+            let product = expression.is_product()?;
+
+            if product.body.contains_variable(&product.index) || !is_nonempty_range(&product.from, &product.to) {
+                return None;
+            }
+
+            Some(Exponentiation::new(product.body, count(&product.from, &product.to)).into())
+        }
+    },
+    name: "Series Constant",
+    description: "sum[i = a, b] c = (b - a + 1) * c"
+};
+
+pub static GAUSS_SUM: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let sum = expression.is_sum()?;
+
+        if sum.body.get_variable()? != sum.index || !starts_at_zero_or_one(&sum.from, &sum.to) {
+            return None;
+        }
+
+        Some(Multiplication::new(
+            WrappedRational::new(1, 2).into(),
+            Multiplication::new(sum.to.clone(), successor(&sum.to)).into(),
+        ).into())
+    },
+    name: "Gauss Sum",
+    description: "sum[i = 1, n] i = 1/2 * n * (n + 1)"
+};
+
+pub static SQUARE_SUM: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let sum = expression.is_sum()?;
+        let exp = sum.body.is_exponentiation()?;
+
+        if exp.left.get_variable()? != sum.index || exp.right.is_natural()?.get() != 2 || !starts_at_zero_or_one(&sum.from, &sum.to) {
+            return None;
+        }
+
+        let n = sum.to;
+
+        Some(Multiplication::new(
+            WrappedRational::new(1, 6).into(),
+            Multiplication::new(
+                Multiplication::new(n.clone(), successor(&n)).into(),
+                successor(&Multiplication::new(WrappedNatural::new(2).into(), n).into()),
+            ).into(),
+        ).into())
+    },
+    name: "Sum of Squares",
+    description: "sum[i = 1, n] i ** 2 = 1/6 * n * (n + 1) * (2 * n + 1)"
+};
@@ -0,0 +1,30 @@
+use crate::{Expressions, Rule};
+
+pub static SURD_EXTRACTION: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let canonical = Expressions::new(expression.is_surd()?.canonical()?.into());
+
+        if canonical == *expression {
+            None
+        } else {
+            Some(canonical.to_inner())
+        }
+    },
+    name: "Surd Extraction",
+    description: "sqrt(12) = 2 * sqrt(3)"
+};
+
+pub static ROOT_SQUARE: Rule = Rule {
+    matches: |expression: &Expressions| {
+        let mul = expression.is_multiplication()?;
+        let radicand = mul.left.is_function_call()?.argument_of("sqrt")?;
+
+        if radicand.is_natural().is_some() && mul.right.is_function_call()?.argument_of("sqrt")? == radicand {
+            Some(radicand.to_inner())
+        } else {
+            None
+        }
+    },
+    name: "Root Square",
+    description: "sqrt(a) * sqrt(a) = a"
+};
@@ -0,0 +1,188 @@
+//! Saving and restoring whole derivations.
+
+use serde_json::{json, Value};
+
+use crate::{json, Definition, Error, State, Step};
+
+/// Version of the session format, bumped on every incompatible change
+/// to it or to the expression schema it embeds
+pub const SESSION_VERSION: u64 = 3;
+
+/// Serializes the whole state
+pub fn save(state: &State) -> String {
+    let history: Vec<Value> = state.history.iter()
+        .map(|step| json!({ "equation": json::to_value(&step.equation), "rule": step.rule }))
+        .collect();
+
+    let definitions: Vec<Value> = state.definitions.iter()
+        .map(|definition| json!({
+            "name": definition.name,
+            "parameters": definition.parameters,
+            "body": json::to_value(&definition.body),
+        }))
+        .collect();
+
+    let document = json!({
+        "format": "ruth-session",
+        "version": SESSION_VERSION,
+        "current": json::to_value(&state.current),
+        "selection": state.selection_path,
+        "history": history,
+        "definitions": definitions,
+        "render_2d": state.render_2d,
+        "verbose": state.verbose,
+        "precision": state.precision,
+    });
+
+    serde_json::to_string_pretty(&document).unwrap()
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, Error> {
+    value.get(name).ok_or_else(|| Error::format(format!("Missing field \"{name}\"")))
+}
+
+fn array_field<'a>(value: &'a Value, name: &str) -> Result<&'a Vec<Value>, Error> {
+    field(value, name)?.as_array().ok_or_else(|| Error::format(format!("Field \"{name}\" is not an array")))
+}
+
+fn boolean_field(value: &Value, name: &str) -> Result<bool, Error> {
+    field(value, name)?.as_bool().ok_or_else(|| Error::format(format!("Field \"{name}\" is not a boolean")))
+}
+
+fn string(value: &Value) -> Result<String, Error> {
+    value.as_str().map(str::to_string).ok_or_else(|| Error::format(format!("{value} is not a string")))
+}
+
+fn step(value: &Value) -> Result<Step, Error> {
+    let rule = match field(value, "rule")? {
+        Value::Null => None,
+        rule => Some(string(rule)?),
+    };
+
+    Ok(Step { equation: json::from_value(field(value, "equation")?)?, rule })
+}
+
+fn definition(value: &Value) -> Result<Definition, Error> {
+    Definition::new(
+        string(field(value, "name")?)?,
+        array_field(value, "parameters")?.iter().map(string).collect::<Result<_, _>>()?,
+        json::from_value(field(value, "body")?)?,
+    ).map_err(|error| Error::format(format!("Invalid definition: {error}")))
+}
+
+/// Restores a saved state, rejecting other versions of the format
+pub fn open(text: &str) -> Result<State, Error> {
+    let document: Value = serde_json::from_str(text).map_err(|error| Error::format(format!("Invalid session file: {error}")))?;
+
+    if document.get("format").and_then(Value::as_str) != Some("ruth-session") {
+        return Err(Error::format("Not a session file"));
+    }
+
+    match field(&document, "version")?.as_u64() {
+        Some(SESSION_VERSION) => {}
+        Some(version) => return Err(Error::format(format!("Unsupported session version {version}, expected {SESSION_VERSION}"))),
+        None => return Err(Error::format("Field \"version\" is not a number")),
+    }
+
+    let current = json::from_value(field(&document, "current")?)?;
+
+    let path = array_field(&document, "selection")?.iter()
+        .map(|index| index.as_u64().map(|index| index as usize).ok_or_else(|| Error::format(format!("{index} is not a child index"))))
+        .collect::<Result<Vec<usize>, Error>>()?;
+
+    let history = array_field(&document, "history")?.iter().map(step).collect::<Result<Vec<Step>, Error>>()?;
+    if history.is_empty() {
+        return Err(Error::format("Session has an empty history"));
+    }
+
+    if current.at(&path).is_none() {
+        return Err(Error::format(format!("Selection {path:?} is not in the equation")));
+    }
+
+    Ok(State {
+        selection_path: path,
+        current,
+        history,
+        definitions: array_field(&document, "definitions")?.iter().map(definition).collect::<Result<_, _>>()?,
+        render_2d: boolean_field(&document, "render_2d")?,
+        verbose: boolean_field(&document, "verbose")?,
+        precision: field(&document, "precision")?.as_u64().map(|digits| digits as usize)
+            .ok_or_else(|| Error::format("Field \"precision\" is not a number of digits"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    fn state() -> State {
+        let mut state = State::new(parse("(a + b) * c").unwrap());
+        state.apply(parse("a * c + b * c").unwrap(), "Distributivity");
+        state.selection_path = vec![1];
+        state.render_2d = true;
+        state.verbose = true;
+        state.precision = 30;
+        state.definitions.push(Definition::parse("f(x) = x ** 2").unwrap());
+        state
+    }
+
+    #[test]
+    fn round_trip() {
+        let saved = state();
+        let opened = open(&save(&saved)).unwrap();
+
+        assert_eq!(opened.current, saved.current);
+        assert_eq!(opened.selection_path, vec![1]);
+        assert_eq!(opened.selection().to_string(), "b * c");
+        assert!(opened.render_2d);
+        assert!(opened.verbose);
+        assert_eq!(opened.precision, 30);
+
+        let steps: Vec<(String, Option<String>)> = opened.history.iter().map(|step| (step.equation.to_string(), step.rule.clone())).collect();
+        assert_eq!(steps, [("(a + b) * c".to_string(), None), ("a * c + b * c".to_string(), Some("Distributivity".to_string()))]);
+
+        assert_eq!(opened.definitions[0].to_string(), "f(x) = x ** 2");
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let document = save(&state()).replace("\"version\": 3", "\"version\": 7");
+        assert_eq!(open(&document).err().unwrap(), Error::format("Unsupported session version 7, expected 3"));
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert_eq!(open("{\"version\": 1}").err().unwrap(), Error::format("Not a session file"));
+        assert!(open("not json").err().unwrap().to_string().starts_with("Invalid session file"));
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let saved = save(&state());
+
+        let document = saved.replace("\"parameters\": [\n        \"x\"\n      ]", "\"parameters\": [\n        \"x\",\n        \"x\"\n      ]");
+        assert_eq!(open(&document).err().unwrap(), Error::format("Invalid definition: Duplicate parameter: x"));
+
+        let document = saved.replace("\"name\": \"f\"", "\"name\": \"sqrt\"");
+        assert_eq!(open(&document).err().unwrap(), Error::format("Invalid definition: Cannot redefine builtin function: sqrt"));
+
+        let document = saved.replace("\"name\": \"f\"", "\"name\": \"pi\"");
+        assert_eq!(open(&document).err().unwrap(), Error::format("Invalid definition: Cannot redefine builtin constant: pi"));
+    }
+
+    #[test]
+    fn rejects_rationals_out_of_range() {
+        let mut state = state();
+        state.load(parse("1/2").unwrap());
+
+        let document = save(&state).replace("\"denominator\": 2", "\"denominator\": -9223372036854775808");
+        assert!(open(&document).err().unwrap().to_string().contains("Overflow in 1/-9223372036854775808"));
+    }
+
+    #[test]
+    fn rejects_selections_outside_the_equation() {
+        let document = save(&state()).replace("\"selection\": [\n    1\n  ]", "\"selection\": [\n    5\n  ]");
+        assert_eq!(open(&document).err().unwrap(), Error::format("Selection [5] is not in the equation"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Numeric check that applicable rules preserve the value of an expression.
+
+use crate::{definitions::Definition, operations::INTEGRATION_CONSTANT, rules::{self, parallel_map}, Expressions, Types, Wrapper, WrappedReal};
+
+// Values given to the variables, chosen away from special points like 0 and 1
+const SAMPLES: [f64; 4] = [0.37, 1.61, 2.23, 0.83];
+
+// Allowed relative difference between both sides, covers rounding of reals
+const TOLERANCE: f64 = 1e-6;
+
+/// Rule whose result evaluates to a different value than the subexpression it rewrites
+#[derive(Debug, Clone)]
+pub struct Unsound {
+    pub path: Vec<usize>,
+    pub rule: &'static str,
+    pub before: Expressions,
+    pub after: Expressions,
+    pub values: (f64, f64),
+}
+
+impl std::fmt::Display for Unsound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {:?}: {} = {} but {} = {}", self.rule, self.path, self.before, self.values.0, self.after, self.values.1)
+    }
+}
+
+fn value(types: &Types) -> Option<f64> {
+    match types {
+        Types::Natural(Wrapper::Constant(natural)) => Some(natural.get() as f64),
+        Types::Zahl(Wrapper::Constant(zahl)) => Some(zahl.get() as f64),
+        Types::Rational(Wrapper::Constant(rational)) => Some(rational.numerator() as f64 / rational.denominator() as f64),
+        Types::Real(Wrapper::Constant(real)) => Some(real.get()),
+        _ => None,
+    }
+}
+
+// Derivatives and indefinite integrals are functions of their variable, substituting
+// a sample for it would evaluate them at a constant. The integration constant is arbitrary
+fn symbolic(expression: &Expressions) -> bool {
+    expression.is_derivative().is_some()
+        || expression.is_integral().is_some_and(|integral| integral.bounds.is_none())
+        || expression.get_variable().is_some_and(|name| name == INTEGRATION_CONSTANT)
+        || expression.get_children().iter().any(symbolic)
+}
+
+// Both sides under the same assignment of the sample values, None if either can't be evaluated
+fn evaluate(before: &Expressions, after: &Expressions, sample: usize) -> Option<(f64, f64)> {
+    let mut variables = before.variables();
+    variables.extend(after.variables());
+    variables.sort();
+    variables.dedup();
+
+    let substitutions: Vec<(String, Expressions)> = variables.into_iter().enumerate()
+        .map(|(i, name)| (name, WrappedReal::new(SAMPLES[(sample + i) % SAMPLES.len()]).into()))
+        .collect();
+
+    let before = value(&before.substitute_all(&substitutions).numeric().ok()?)?;
+    let after = value(&after.substitute_all(&substitutions).numeric().ok()?)?;
+
+    Some((before, after))
+}
+
+fn agrees((before, after): (f64, f64)) -> bool {
+    (before - after).abs() <= TOLERANCE * before.abs().max(after.abs()).max(1.0)
+}
+
+/// Checks every rule applicable anywhere in the expression on all worker threads.
+///
+/// Returns the total number of matches and the ones that changed the value.
+/// Matches that can't be evaluated, like derivatives and indefinite integrals, are counted as sound
+pub fn check(expression: &Expressions, definitions: &[Definition]) -> (usize, Vec<Unsound>) {
+    let matches = rules::find_rules_in_tree(expression, definitions);
+
+    let unsound = parallel_map(&matches, |(path, found)| {
+        let before = expression.at(path).unwrap();
+        let after = found.result();
+
+        if symbolic(&before) || symbolic(&after) {
+            return None;
+        }
+
+        (0..SAMPLES.len())
+            .filter_map(|sample| evaluate(&before, &after, sample))
+            .find(|values| !agrees(*values))
+            .map(|values| Unsound { path: path.clone(), rule: found.name, before: before.clone(), after, values })
+    });
+
+    (matches.len(), unsound.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, Derivative, Integral};
+    use super::*;
+
+    #[test]
+    fn sound_rules_pass() {
+        let (matches, unsound) = check(&parse("(a + b) * c + sin(x) ** 2").unwrap(), &[]);
+
+        assert!(matches > 0);
+        assert!(unsound.is_empty(), "{unsound:?}");
+    }
+
+    #[test]
+    fn derivatives_are_not_sampled() {
+        let derivative: Expressions = Derivative::new(parse("x ** 3").unwrap(), "x").into();
+        let (matches, unsound) = check(&derivative, &[]);
+
+        assert!(rules::find_all_rules(&derivative, &[]).iter().any(|found| found.name == "Power Rule"));
+        assert!(matches > 0);
+        assert!(unsound.is_empty(), "{unsound:?}");
+    }
+
+    #[test]
+    fn indefinite_integrals_are_not_sampled() {
+        let integral: Expressions = Integral::new(parse("2 * x + 1").unwrap(), "x").into();
+        let (matches, unsound) = check(&integral, &[]);
+
+        assert!(matches > 0);
+        assert!(unsound.is_empty(), "{unsound:?}");
+    }
+
+    #[test]
+    fn variables_named_c_are_sampled() {
+        assert!(!symbolic(&parse("x + C").unwrap()));
+        assert!(symbolic(&Integral::with_constant(parse("x").unwrap())));
+    }
+
+    #[test]
+    fn definite_integrals_are_sampled() {
+        let integral: Expressions = Integral::new_definite(parse("x ** 2").unwrap(), "x", parse("0").unwrap(), parse("a").unwrap()).into();
+        let found = rules::find_all_rules(&integral, &[]);
+
+        assert!(!found.is_empty());
+        assert!(evaluate(&integral, &found[0].result(), 0).is_some());
+        assert!(check(&integral, &[]).1.is_empty());
+    }
+
+    #[test]
+    fn value_changes_are_reported() {
+        let before = parse("a + b").unwrap();
+
+        assert!(agrees(evaluate(&before, &parse("b + a").unwrap(), 0).unwrap()));
+        assert!(!agrees(evaluate(&before, &parse("a * b").unwrap(), 0).unwrap()));
+    }
+}
@@ -0,0 +1,106 @@
+//! State of a derivation and its history.
+
+use crate::{definitions::Definition, render, Expressions};
+
+/// Equation in the history and the rule or command that produced it
+pub struct Step {
+    pub equation: Expressions,
+    pub rule: Option<String>,
+}
+
+impl Step {
+    pub fn new(equation: Expressions) -> Self {
+        Self { equation, rule: None }
+    }
+}
+
+/// Derivation in progress: the equation, the selected subexpression and how it was reached
+pub struct State {
+    /// Current working equation
+    pub current: Expressions,
+
+    /// Child indices from the current equation to the selection
+    pub selection_path: Vec<usize>,
+
+    /// History of equations, sharing unchanged subtrees
+    pub history: Vec<Step>,
+
+    /// User definitions, kept across loaded equations
+    pub definitions: Vec<Definition>,
+
+    /// Print equation and selection in two dimensions
+    pub render_2d: bool,
+
+    /// Print every operation in parentheses instead of only where needed
+    pub verbose: bool,
+
+    /// Significant decimal digits of numeric evaluation, 0 computes with f64
+    pub precision: usize,
+}
+
+impl State {
+    /// Starts a derivation of the equation with the whole equation selected
+    pub fn new(equation: Expressions) -> Self {
+        Self {
+            history: vec![Step::new(equation.clone())],
+            definitions: vec![],
+            render_2d: false,
+            verbose: false,
+            precision: 0,
+            selection_path: vec![],
+            current: equation,
+        }
+    }
+
+    /// Replaces the equation and starts a new history, definitions are kept
+    pub fn load(&mut self, equation: Expressions) {
+        self.history = vec![Step::new(equation.clone())];
+        self.selection_path = vec![];
+        self.current = equation;
+    }
+
+    /// Selected subexpression of the current equation
+    pub fn selection(&self) -> Expressions {
+        self.current.at(&self.selection_path).unwrap_or_else(|| self.current.clone())
+    }
+
+    /// Replaces the selection and records the new equation in the history
+    pub fn apply(&mut self, result: Expressions, rule: &str) {
+        self.current = self.current.replace_at(&self.selection_path, result);
+        self.selection_path = vec![];
+
+        self.history.push(Step { equation: self.current.clone(), rule: Some(rule.to_string()) });
+    }
+
+    /// Expression as text with only needed or, in verbose mode, all parentheses
+    pub fn format(&self, expression: &Expressions) -> String {
+        if self.verbose {
+            format!("{expression:+}")
+        } else {
+            expression.to_string()
+        }
+    }
+
+    /// Prints the equation and the selection
+    pub fn print_equation(&self) {
+        self.print_labeled("Equation: ", &self.current);
+        self.print_labeled("Selection: ", &self.selection());
+    }
+
+    fn print_labeled(&self, label: &str, expression: &Expressions) {
+        if !self.render_2d {
+            println!("{label}{}", self.format(expression));
+            return;
+        }
+
+        let block = render::render(expression);
+
+        for (i, line) in block.to_string().lines().enumerate() {
+            if i == block.baseline() {
+                println!("{label}{line}");
+            } else {
+                println!("{}{line}", " ".repeat(label.len()));
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Exact square roots of naturals in the form `k * sqrt(n)`.
+
+use crate::{Expressions, FunctionCall, InnerExpressions, Multiplication, WrappedNatural};
+
+/// Square root k * sqrt(n), canonical if the radicand n is square free
+#[derive(Debug, Clone, PartialEq)]
+pub struct Surd {
+    pub coefficient: u32,
+    pub radicand: u32,
+}
+
+impl Surd {
+    /// sqrt(value) with every square factor of the value moved into the coefficient
+    pub fn of(value: u32) -> Self {
+        let mut surd = Surd { coefficient: 1, radicand: value };
+        let mut factor = 2;
+
+        while factor <= surd.radicand / factor {
+            if surd.radicand.is_multiple_of(factor * factor) {
+                surd.coefficient *= factor;
+                surd.radicand /= factor * factor;
+            } else {
+                factor += 1;
+            }
+        }
+
+        surd
+    }
+
+    /// The same number with a square free radicand, None if the coefficient overflows
+    pub fn canonical(&self) -> Option<Self> {
+        let root = Surd::of(self.radicand);
+
+        Some(Surd { coefficient: self.coefficient.checked_mul(root.coefficient)?, radicand: root.radicand })
+    }
+}
+
+// Omits a coefficient of 1 and the root of 1, so sqrt(4) becomes 2
+impl From<Surd> for InnerExpressions {
+    fn from(surd: Surd) -> Self {
+        let root: Expressions = FunctionCall::new("sqrt", vec![WrappedNatural::new(surd.radicand).into()]).into();
+
+        match (surd.coefficient, surd.radicand) {
+            (coefficient, 1) => WrappedNatural::new(coefficient).into(),
+            (1, _) => root.to_inner(),
+            (coefficient, _) => Multiplication::new(WrappedNatural::new(coefficient).into(), root).into(),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Tree printout of expressions with types and errors.
+
+use crate::{operations::{Operation, OperationTrait}, visit::Fold, Error, Expressions, TypeTrait, Types};
+
+// Kind of a constant or variable, like "Variable b"
+fn type_kind(types: &Types) -> String {
+    if types.is_value() {
+        format!("Constant {}", types)
+    } else if types.is_variable() {
+        format!("Variable {}", types)
+    } else {
+        format!("Expression {}", types)
+    }
+}
+
+// Kind of the operation without its children, like "Sum over i"
+fn operation_kind(operation: &Operation) -> String {
+    match operation {
+        Operation::Addition(_) => "Addition".to_string(),
+        Operation::Subtraction(_) => "Subtraction".to_string(),
+        Operation::Multiplication(_) => "Multiplication".to_string(),
+        Operation::Division(_) => "Division".to_string(),
+        Operation::Exponentiation(_) => "Exponentiation".to_string(),
+        Operation::Derivative(derivative) => format!("Derivative d/d{}", derivative.variable),
+        Operation::Integral(integral) if integral.bounds.is_some() => format!("Definite Integral d{}", integral.variable),
+        Operation::Integral(integral) => format!("Integral d{}", integral.variable),
+        Operation::FunctionCall(call) => format!("Function {}", call.name),
+        Operation::Sum(sum) => format!("Sum over {}", sum.index),
+        Operation::Product(product) => format!("Product over {}", product.index),
+    }
+}
+
+// Node of the printout with the kind and value of its expression
+struct Node {
+    kind: String,
+    value: Result<Types, Error>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    // Inferred type, or where solving failed. Errors of children are only
+    // reported at the child that caused them.
+    fn inferred_type(&self) -> String {
+        match &self.value {
+            Ok(types) => types.get_type().to_string(),
+            Err(_) if self.children.iter().any(|child| child.value.is_err()) => "error in child".to_string(),
+            Err(error) => format!("error: {error}"),
+        }
+    }
+
+    fn write(&self, output: &mut String, label: &str, prefix: &str, child_prefix: &str) {
+        let marker = if self.value.is_err() { "✗ " } else { "" };
+        output.push_str(&format!("{prefix}{label}{marker}{}: {}\n", self.kind, self.inferred_type()));
+
+        for (i, child) in self.children.iter().enumerate() {
+            let last = i + 1 == self.children.len();
+            let (branch, indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+
+            child.write(output, &format!("{i}: "), &format!("{child_prefix}{branch}"), &format!("{child_prefix}{indent}"));
+        }
+    }
+}
+
+// Solves every node once, from the values of its children like Expressions::solve
+struct Typed;
+
+impl Fold for Typed {
+    type Output = Node;
+
+    fn fold_type(&mut self, types: &Types) -> Node {
+        Node { kind: type_kind(types), value: Ok(types.solve()), children: vec![] }
+    }
+
+    fn fold_operation(&mut self, operation: &Operation, children: Vec<Node>) -> Node {
+        let values: Result<Vec<Types>, Error> = children.iter()
+            .enumerate()
+            .map(|(i, child)| child.value.clone().map_err(|error| error.in_child(i)))
+            .collect();
+
+        let value = values.and_then(|values| operation.evaluate(values)).map(Types::demote);
+        Node { kind: operation_kind(operation), value, children }
+    }
+}
+
+/// Indented tree with the same child indices as the children command
+pub fn print_tree(expression: &Expressions) -> String {
+    let mut output = String::new();
+    Typed.fold(expression).write(&mut output, "", "", "");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+    use super::*;
+
+    fn tree(input: &str) -> String {
+        print_tree(&parse(input).unwrap())
+    }
+
+    #[test]
+    fn kinds_of_nodes() {
+        assert_eq!(tree("3"), "Constant 3: Natural\n");
+        assert_eq!(tree("b"), "Variable b: Real\n");
+        assert!(tree("sum(i, 1, 3, i)").starts_with("Sum over i: Natural\n"));
+    }
+
+    #[test]
+    fn children_are_indented_with_their_index() {
+        assert_eq!(tree("(a + 1) * f(2)"), [
+            "Multiplication: Real",
+            "├── 0: Addition: Real",
+            "│   ├── 0: Variable a: Real",
+            "│   └── 1: Constant 1: Natural",
+            "└── 1: Function f: Real",
+            "    └── 0: Constant 2: Natural",
+            "",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn errors_are_reported_at_their_origin() {
+        assert_eq!(tree("x + 2 ** 4294967295"), [
+            "✗ Addition: error in child",
+            "├── 0: Variable x: Real",
+            "└── 1: ✗ Exponentiation: error: Overflow in 2 ** 4294967295",
+            "    ├── 0: Constant 2: Natural",
+            "    └── 1: Constant 4294967295: Natural",
+            "",
+        ].join("\n"));
+    }
+}
@@ -1,3 +1,5 @@
+//! Number types and their wrapping into constants, variables and expressions.
+
 mod natural;
 use enum_dispatch::enum_dispatch;
 pub use natural::*;
@@ -5,20 +7,30 @@ pub use natural::*;
 mod zahl;
 pub use zahl::*;
 
+mod rational;
+pub use rational::*;
+
 mod real;
 pub use real::*;
 
+mod complex;
+pub use complex::*;
+
+mod tower;
+pub use tower::*;
+
 use std::fmt::{Debug, Display};
 
-use crate::{Expressions, InnerExpressions};
+use crate::{operations::Precedence, Expressions, InnerExpressions};
 
 #[enum_dispatch]
 pub trait TypeTrait {
     fn is_value(&self) -> bool;
     fn is_variable(&self) -> bool;
+    fn get_variable(&self) -> Option<&str>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Wrapper<T> {
     Constant(T),
     Variable(String),
@@ -37,40 +49,25 @@ impl<T> TypeTrait for Wrapper<T> {
     }
 
     fn is_variable(&self) -> bool {
-        matches!(self, Wrapper::Constant(_))
-    }
-}
-
-pub trait MyFrom<From> {
-    fn my_from(from: From) -> Self;
-}
-
-pub trait MyInto<Into> {
-    fn my_into(self) -> Into;
-}
-
-impl<From, Into> MyInto<Into> for From where Into: MyFrom<From> {
-    fn my_into(self) -> Into {
-        Into::my_from(self)
+        matches!(self, Wrapper::Variable(_))
     }
-} 
 
-impl<T, O> MyFrom<Wrapper<T>> for Wrapper<O> where T: MyInto<O> {
-    fn my_from(from: Wrapper<T>) -> Self {
-        match from {
-            Wrapper::Constant(inner) => Wrapper::Constant(inner.my_into()),
-            Wrapper::Variable(name) => Wrapper::Variable(name.clone()),
-            Wrapper::Expression(expression) => Wrapper::Expression(expression.clone()),
+    fn get_variable(&self) -> Option<&str> {
+        match self {
+            Wrapper::Variable(name) => Some(name),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[enum_dispatch(TypeTrait)]
 pub enum Types {
     Natural(WrappedNatural),
     Zahl(WrappedZahl),
+    Rational(WrappedRational),
     Real(WrappedReal),
+    Complex(WrappedComplex),
 }
 
 impl<T: Display> Display for Wrapper<T> {
@@ -89,6 +86,8 @@ impl Display for Types {
             Types::Real(real) => Display::fmt(&real, f),
             Types::Natural(natural) => Display::fmt(&natural, f),
             Types::Zahl(zahl) => Display::fmt(&zahl, f),
+            Types::Rational(rational) => Display::fmt(&rational, f),
+            Types::Complex(complex) => Display::fmt(&complex, f),
         }
     }
 }
@@ -99,6 +98,8 @@ impl Types {
             Types::Real(wrapped) => wrapped.get_type(),
             Types::Natural(wrapped) => wrapped.get_type(),
             Types::Zahl(wrapped) => wrapped.get_type(),
+            Types::Rational(wrapped) => wrapped.get_type(),
+            Types::Complex(wrapped) => wrapped.get_type(),
         }
     }
 
@@ -106,13 +107,82 @@ impl Types {
         vec![]
     }
 
-    pub fn copy(&self) -> Expressions {
-        self.clone().into()
-    }
-
     pub fn solve(&self) -> Types {
         self.clone()
     }
+
+    /// Negative numbers print with a sign and fractions with a bar
+    pub fn precedence(&self) -> Precedence {
+        match self {
+            Types::Zahl(Wrapper::Constant(zahl)) if zahl.get() < 0 => Precedence::Sum,
+            Types::Real(Wrapper::Constant(real)) if real.get() < 0.0 => Precedence::Sum,
+            Types::Rational(Wrapper::Constant(rational)) if rational.numerator() < 0 => Precedence::Sum,
+            Types::Rational(Wrapper::Constant(rational)) if rational.denominator() != 1 => Precedence::Product,
+            Types::Complex(Wrapper::Constant(complex)) => match complex.parts() {
+                (real, 0.0) if real < 0.0 => Precedence::Sum,
+                (_, 0.0) => Precedence::Atom,
+                (0.0, imaginary) if imaginary < 0.0 => Precedence::Sum,
+                (0.0, _) if complex.is_unit() => Precedence::Atom,
+                (0.0, _) => Precedence::Product,
+                _ => Precedence::Sum,
+            },
+            _ => Precedence::Atom,
+        }
+    }
+
+    /// Whether the value is a negative constant
+    pub fn is_negative(&self) -> bool {
+        match self {
+            Types::Zahl(Wrapper::Constant(zahl)) => zahl.get() < 0,
+            Types::Rational(Wrapper::Constant(rational)) => rational.numerator() < 0,
+            Types::Real(Wrapper::Constant(real)) => real.get() < 0.0,
+            _ => false,
+        }
+    }
+
+    /// Whether the value is the constant 0 of any type
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Types::Natural(Wrapper::Constant(natural)) => natural.get() == 0,
+            Types::Zahl(Wrapper::Constant(zahl)) => zahl.get() == 0,
+            Types::Rational(Wrapper::Constant(rational)) => rational.numerator() == 0,
+            Types::Real(Wrapper::Constant(real)) => real.get() == 0.0,
+            Types::Complex(Wrapper::Constant(complex)) => complex.is_zero(),
+            _ => false,
+        }
+    }
+
+    /// Whether the value is the constant 1 of any type
+    pub fn is_one(&self) -> bool {
+        match self {
+            Types::Natural(Wrapper::Constant(natural)) => natural.get() == 1,
+            Types::Zahl(Wrapper::Constant(zahl)) => zahl.get() == 1,
+            Types::Rational(Wrapper::Constant(rational)) => rational.numerator() == 1 && rational.denominator() == 1,
+            Types::Real(Wrapper::Constant(real)) => real.get() == 1.0,
+            Types::Complex(Wrapper::Constant(complex)) => complex.parts() == (1.0, 0.0),
+            _ => false,
+        }
+    }
+
+    /// Whether the value is a constant without fractional part
+    /// Value of an integer constant of the exact types
+    pub fn to_integer(&self) -> Option<i64> {
+        match self {
+            Types::Natural(Wrapper::Constant(natural)) => Some(natural.get().into()),
+            Types::Zahl(Wrapper::Constant(zahl)) => Some(zahl.get().into()),
+            Types::Rational(Wrapper::Constant(rational)) if rational.denominator() == 1 => Some(rational.numerator()),
+            _ => None,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Types::Natural(Wrapper::Constant(_)) | Types::Zahl(Wrapper::Constant(_)) => true,
+            Types::Rational(Wrapper::Constant(rational)) => rational.denominator() == 1,
+            Types::Real(Wrapper::Constant(real)) => real.get().fract() == 0.0,
+            _ => false,
+        }
+    }
 }
 
 impl<T> From<T> for Wrapper<T> {
@@ -0,0 +1,237 @@
+use crate::{operations::{Add, Div, Exp, Mul, Sub}, Error};
+use super::{rational::Rational, real::{precision, Real}, Wrapper};
+
+/// Complex number with exact fractions as parts, or approximate reals once any part was inexact
+#[derive(Debug, Clone, PartialEq)]
+pub enum Complex {
+    Exact(Rational, Rational),
+    Approximate(Real, Real),
+}
+
+impl Complex {
+    /// The imaginary unit i
+    pub fn unit() -> Self {
+        Complex::Exact(Rational::new(0, 1), Rational::new(1, 1))
+    }
+
+    pub fn approximate(real: f64, imaginary: f64) -> Self {
+        Complex::Approximate(Real::new(real), Real::new(imaginary))
+    }
+
+    pub fn is_unit(&self) -> bool {
+        *self == Complex::unit()
+    }
+
+    /// Real and imaginary part, rounded for exact numbers
+    pub fn parts(&self) -> (f64, f64) {
+        match self {
+            Complex::Exact(real, imaginary) => (fraction(real), fraction(imaginary)),
+            Complex::Approximate(real, imaginary) => (real.get(), imaginary.get()),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.parts() == (0.0, 0.0)
+    }
+
+    // Both numbers with approximate parts of the current precision, for operations where one of them is inexact
+    fn approximated(&self) -> (Real, Real) {
+        match self {
+            Complex::Exact(real, imaginary) => (
+                Real::fraction(real.numerator(), real.denominator()),
+                Real::fraction(imaginary.numerator(), imaginary.denominator()),
+            ),
+            Complex::Approximate(real, imaginary) => (real.clone(), imaginary.clone()),
+        }
+    }
+
+    // 1 / z = conj(z) / |z|²
+    fn reciprocal(self) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Err(Error::division_by_zero());
+        }
+
+        match self {
+            Complex::Exact(real, imaginary) => {
+                let norm = real.clone().mul(real.clone())?.add(imaginary.clone().mul(imaginary.clone())?)?;
+                let inverse = Rational::new(norm.denominator(), norm.numerator());
+
+                Ok(Complex::Exact(real.mul(inverse.clone())?, Rational::new(0, 1).sub(imaginary)?.mul(inverse)?))
+            }
+            Complex::Approximate(real, imaginary) => {
+                let inverse = real.clone().mul(real.clone())?.add(imaginary.clone().mul(imaginary.clone())?)?.reciprocal()?;
+
+                Ok(Complex::Approximate(real.mul(inverse.clone())?, Real::fraction(0, 1).sub(imaginary)?.mul(inverse)?))
+            }
+        }
+    }
+}
+
+fn fraction(rational: &Rational) -> f64 {
+    rational.numerator() as f64 / rational.denominator() as f64
+}
+
+pub type WrappedComplex = Wrapper<Complex>;
+
+impl WrappedComplex {
+    pub fn new(complex: Complex) -> Self {
+        Self::Constant(complex)
+    }
+
+    pub fn get_type(&self) -> &str {
+        "Complex"
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self, rhs) {
+            (Complex::Exact(a, b), Complex::Exact(c, d)) => Ok(Complex::Exact(a.add(c)?, b.add(d)?)),
+            (left, right) => {
+                let ((a, b), (c, d)) = (left.approximated(), right.approximated());
+                Ok(Complex::Approximate(a.add(c)?, b.add(d)?))
+            }
+        }
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self, rhs) {
+            (Complex::Exact(a, b), Complex::Exact(c, d)) => Ok(Complex::Exact(a.sub(c)?, b.sub(d)?)),
+            (left, right) => {
+                let ((a, b), (c, d)) = (left.approximated(), right.approximated());
+                Ok(Complex::Approximate(a.sub(c)?, b.sub(d)?))
+            }
+        }
+    }
+}
+
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self, rhs) {
+            (Complex::Exact(a, b), Complex::Exact(c, d)) => Ok(Complex::Exact(
+                a.clone().mul(c.clone())?.sub(b.clone().mul(d.clone())?)?,
+                a.mul(d)?.add(b.mul(c)?)?,
+            )),
+            (left, right) => {
+                let ((a, b), (c, d)) = (left.approximated(), right.approximated());
+
+                Ok(Complex::Approximate(
+                    a.clone().mul(c.clone())?.sub(b.clone().mul(d.clone())?)?,
+                    a.mul(d)?.add(b.mul(c)?)?,
+                ))
+            }
+        }
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    fn div(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.mul(rhs.reciprocal()?)
+    }
+}
+
+impl Complex {
+    // Repeated squaring keeps exact numbers exact
+    fn power(self, mut exponent: u32) -> Result<Self, Error> {
+        let mut result = Complex::Exact(Rational::new(1, 1), Rational::new(0, 1));
+        let mut base = self;
+
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(base.clone())?;
+            }
+
+            exponent /= 2;
+
+            if exponent > 0 {
+                base = base.clone().mul(base)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+// Integer powers are exact, all others are the principal value z ** w = exp(w * ln z)
+impl Exp for Complex {
+    type Output = Complex;
+
+    fn exp(self, rhs: Self) -> Result<Self::Output, Error> {
+        if let Complex::Exact(exponent, imaginary) = &rhs {
+            if let (0, 1, Ok(power)) = (imaginary.numerator(), exponent.denominator(), u32::try_from(exponent.numerator().unsigned_abs())) {
+                let power = self.power(power)?;
+                return if exponent.numerator() >= 0 { Ok(power) } else { power.reciprocal() };
+            }
+        }
+
+        if self.is_zero() {
+            return if rhs.parts().0 > 0.0 { Ok(Complex::approximate(0.0, 0.0)) } else { Err(Error::division_by_zero()) };
+        }
+
+        let ((a, b), (c, d)) = (self.approximated(), rhs.approximated());
+        let modulus = a.clone().mul(a.clone())?.add(b.clone().mul(b.clone())?)?.sqrt()?.ln()?;
+        let argument = b.atan2(a)?;
+
+        let real = c.clone().mul(modulus.clone())?.sub(d.clone().mul(argument.clone())?)?;
+        let imaginary = c.mul(argument)?.add(d.mul(modulus)?)?;
+        let length = real.exp()?;
+
+        // Parts that are only rounding noise of the angle become 0, so (-4) ** 0.5 = 2i
+        let noise = length.get() * match precision() {
+            0 => f64::EPSILON * 4.0,
+            digits => 10f64.powi(4 - digits as i32),
+        };
+        let part = |value: Real| if value.get().abs() <= noise { Real::fraction(0, 1) } else { value };
+
+        Ok(Complex::Approximate(
+            part(length.clone().mul(imaginary.clone().cos()?)?),
+            part(length.mul(imaginary.sin()?)?),
+        ))
+    }
+}
+
+// Writes b * i, with a coefficient only where needed
+fn fmt_imaginary(f: &mut std::fmt::Formatter<'_>, coefficient: &str) -> std::fmt::Result {
+    match coefficient {
+        "1" => write!(f, "i"),
+        "-1" => write!(f, "-i"),
+        coefficient => write!(f, "{coefficient} * i"),
+    }
+}
+
+fn fmt_parts(f: &mut std::fmt::Formatter<'_>, real: String, imaginary: String) -> std::fmt::Result {
+    match (real.as_str(), imaginary.as_str()) {
+        (_, "0" | "-0") => write!(f, "{real}"),
+        ("0" | "-0", _) => fmt_imaginary(f, &imaginary),
+        (_, _) => match imaginary.strip_prefix('-') {
+            Some(magnitude) => {
+                write!(f, "{real} - ")?;
+                fmt_imaginary(f, magnitude)
+            }
+            None => {
+                write!(f, "{real} + ")?;
+                fmt_imaginary(f, &imaginary)
+            }
+        },
+    }
+}
+
+impl std::fmt::Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Complex::Exact(real, imaginary) => fmt_parts(f, real.to_string(), imaginary.to_string()),
+            Complex::Approximate(real, imaginary) => fmt_parts(f, real.to_string(), imaginary.to_string()),
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{operations::{Add, Exp, Mul, TypeAdd, TypeExp, TypeMul}, WrappedReal, WrappedZahl};
-use super::{MyFrom, MyInto, Types, Wrapper};
+use crate::{operations::{Add, Exp, Mul, Sub}, Error};
+use super::{Wrapper, Zahl};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Natural(u32);
 
 impl Natural {
@@ -26,63 +26,36 @@ impl WrappedNatural {
     }
 }
 
-impl MyFrom<Natural> for Natural {
-    fn my_from(from: Natural) -> Self {
-        from
-    }
-}
-
 impl Add for Natural {
     type Output = Natural;
 
-    fn add(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Natural::new(self.get() + rhs.get()))
+    fn add(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_add(rhs.get()).map(Natural::new).ok_or(Error::overflow(format!("{self} + {rhs}")))
     }
 }
 
-impl TypeAdd for WrappedNatural {
-    fn type_add(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.add(rhs),
-            Types::Zahl(rhs) => MyInto::<WrappedZahl>::my_into(self).add(rhs),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).add(rhs),
-        }
+// The difference of naturals can be negative, so it is an integer
+impl Sub for Natural {
+    type Output = Zahl;
+
+    fn sub(self, rhs: Self) -> Result<Self::Output, Error> {
+        (self.get() as i64 - rhs.get() as i64).try_into().map(Zahl::new).map_err(|_| Error::overflow(format!("{self} - {rhs}")))
     }
 }
 
 impl Mul for Natural {
     type Output = Natural;
 
-    fn mul(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Natural::new(self.get() * rhs.get()))
-    }
-}
-
-impl TypeMul for WrappedNatural {
-    fn type_mul(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.mul(rhs),
-            Types::Zahl(rhs) => MyInto::<WrappedZahl>::my_into(self).mul(rhs),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).mul(rhs),
-        }
+    fn mul(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_mul(rhs.get()).map(Natural::new).ok_or(Error::overflow(format!("{self} * {rhs}")))
     }
 }
 
 impl Exp for Natural {
     type Output = Natural;
 
-    fn exp(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Natural::new((self.get()).pow(rhs.get())))
-    }
-}
-
-impl TypeExp for WrappedNatural {
-    fn type_exp(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.exp(rhs),
-            Types::Zahl(rhs) => MyInto::<WrappedReal>::my_into(self).exp(MyInto::<WrappedReal>::my_into(rhs)),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).exp(rhs),
-        }
+    fn exp(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_pow(rhs.get()).map(Natural::new).ok_or(Error::overflow(format!("{self} ** {rhs}")))
     }
 }
 
@@ -0,0 +1,222 @@
+use crate::{operations::{Add, Div, Exp, Mul, Sub}, Error};
+use super::Wrapper;
+
+/// Always stored in lowest terms with a positive denominator
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+impl Rational {
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        assert!(denominator != 0, "Rational with zero denominator");
+
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Self { numerator: numerator / divisor, denominator: denominator / divisor }
+    }
+
+    /// Like new, but an error for a zero denominator or for i64::MIN, whose sign can't be normalized
+    pub fn try_new(numerator: i64, denominator: i64) -> Result<Self, Error> {
+        if denominator == 0 {
+            return Err(Error::division_by_zero());
+        }
+
+        Self::checked(Some(numerator), Some(denominator), || format!("{numerator}/{denominator}"))
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    // Normalized fraction of checked results, an overflow if any of them failed.
+    // i64::MIN has no positive counterpart, so normalizing its sign could overflow
+    fn checked(numerator: Option<i64>, denominator: Option<i64>, operation: impl FnOnce() -> String) -> Result<Self, Error> {
+        match (numerator, denominator) {
+            (Some(numerator), Some(denominator)) if numerator != i64::MIN && denominator != i64::MIN => Ok(Self::new(numerator, denominator)),
+            _ => Err(Error::overflow(operation())),
+        }
+    }
+
+    fn pow(&self, exponent: u32) -> Result<Self, Error> {
+        Self::checked(
+            self.numerator.checked_pow(exponent),
+            self.denominator.checked_pow(exponent),
+            || format!("({self}) ** {exponent}"),
+        )
+    }
+}
+
+pub type WrappedRational = Wrapper<Rational>;
+
+impl WrappedRational {
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        Self::Constant(Rational::new(numerator, denominator))
+    }
+
+    pub fn get_type(&self) -> &str {
+        "Rational"
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Result<Self::Output, Error> {
+        let numerator = self.numerator.checked_mul(rhs.denominator)
+            .zip(rhs.numerator.checked_mul(self.denominator))
+            .and_then(|(left, right)| left.checked_add(right));
+
+        Rational::checked(numerator, self.denominator.checked_mul(rhs.denominator), || format!("{self} + {rhs}"))
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Result<Self::Output, Error> {
+        let numerator = self.numerator.checked_mul(rhs.denominator)
+            .zip(rhs.numerator.checked_mul(self.denominator))
+            .and_then(|(left, right)| left.checked_sub(right));
+
+        Rational::checked(numerator, self.denominator.checked_mul(rhs.denominator), || format!("{self} - {rhs}"))
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Result<Self::Output, Error> {
+        Rational::checked(
+            self.numerator.checked_mul(rhs.numerator),
+            self.denominator.checked_mul(rhs.denominator),
+            || format!("{self} * {rhs}"),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Self) -> Result<Self::Output, Error> {
+        if rhs.numerator == 0 {
+            return Err(Error::division_by_zero());
+        }
+
+        Rational::checked(
+            self.numerator.checked_mul(rhs.denominator),
+            self.denominator.checked_mul(rhs.numerator),
+            || format!("{self} / {rhs}"),
+        )
+    }
+}
+
+// Integer powers stay exact, fractional powers are left to the reals
+impl Exp for Rational {
+    type Output = Rational;
+
+    fn exp(self, rhs: Self) -> Result<Self::Output, Error> {
+        if rhs.denominator != 1 {
+            return Err(Error::type_mismatch(format!("Exponent of ({self}) ** ({rhs}) is not an integer")));
+        }
+
+        // Powers of 0, 1 and -1 are known for exponents of any size
+        match (self.numerator, self.denominator) {
+            (0, _) if rhs.numerator < 0 => return Err(Error::division_by_zero()),
+            (0, _) if rhs.numerator > 0 => return Ok(self),
+            (1, 1) => return Ok(self),
+            (-1, 1) if rhs.numerator % 2 == 0 => return Ok(Rational::new(1, 1)),
+            (-1, 1) => return Ok(self),
+            _ => {}
+        }
+
+        let exponent = u32::try_from(rhs.numerator.unsigned_abs()).map_err(|_| Error::overflow(format!("({self}) ** {rhs}")))?;
+        let power = self.pow(exponent)?;
+
+        if rhs.numerator >= 0 {
+            Ok(power)
+        } else if power.numerator == 0 {
+            Err(Error::division_by_zero())
+        } else {
+            Ok(Rational::new(power.denominator, power.numerator))
+        }
+    }
+}
+
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_to_lowest_terms() {
+        assert_eq!(Rational::new(6, 8), Rational::new(3, 4));
+        assert_eq!(Rational::new(3, -6).to_string(), "-1/2");
+        assert_eq!(Rational::new(-4, -2).to_string(), "2");
+        assert_eq!(Rational::new(0, -5), Rational::new(0, 1));
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        assert_eq!(Rational::new(1, 2).add(Rational::new(1, 3)).unwrap(), Rational::new(5, 6));
+        assert_eq!(Rational::new(1, 2).sub(Rational::new(1, 2)).unwrap(), Rational::new(0, 1));
+        assert_eq!(Rational::new(2, 3).mul(Rational::new(3, 4)).unwrap(), Rational::new(1, 2));
+        assert_eq!(Rational::new(2, 3).exp(Rational::new(-2, 1)).unwrap(), Rational::new(9, 4));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let large = Rational::new(i64::MAX, 1);
+
+        assert_eq!(large.clone().add(Rational::new(1, 1)), Err(Error::overflow(format!("{large} + 1"))));
+        assert!(large.clone().mul(Rational::new(2, 1)).is_err());
+        assert!(Rational::new(1, i64::MAX).mul(Rational::new(1, 2)).is_err());
+        assert!(Rational::new(2, 1).exp(Rational::new(64, 1)).is_err());
+        assert!(Rational::new(i64::MIN + 1, 1).sub(Rational::new(1, 1)).is_err());
+    }
+
+    #[test]
+    fn fallible_construction() {
+        assert_eq!(Rational::try_new(3, -6), Ok(Rational::new(-1, 2)));
+        assert_eq!(Rational::try_new(1, 0), Err(Error::division_by_zero()));
+        assert_eq!(Rational::try_new(1, i64::MIN), Err(Error::overflow("1/-9223372036854775808")));
+        assert!(Rational::try_new(i64::MIN, 1).is_err());
+    }
+
+    #[test]
+    fn invalid_powers_are_errors() {
+        assert_eq!(Rational::new(0, 1).exp(Rational::new(-1, 1)), Err(Error::division_by_zero()));
+        assert_eq!(
+            Rational::new(2, 1).exp(Rational::new(1, 2)),
+            Err(Error::type_mismatch("Exponent of (2) ** (1/2) is not an integer")),
+        );
+        assert!(Rational::new(2, 1).exp(Rational::new(1 << 40, 1)).is_err());
+    }
+
+    #[test]
+    fn powers_of_units_and_zero() {
+        assert_eq!(Rational::new(1, 1).exp(Rational::new(1 << 40, 1)), Ok(Rational::new(1, 1)));
+        assert_eq!(Rational::new(-1, 1).exp(Rational::new(1 << 40, 1)), Ok(Rational::new(1, 1)));
+        assert_eq!(Rational::new(-1, 1).exp(Rational::new(-(1 << 40) - 1, 1)), Ok(Rational::new(-1, 1)));
+        assert_eq!(Rational::new(0, 1).exp(Rational::new(1 << 40, 1)), Ok(Rational::new(0, 1)));
+        assert_eq!(Rational::new(0, 1).exp(Rational::new(-(1 << 40), 1)), Err(Error::division_by_zero()));
+        assert_eq!(Rational::new(0, 1).exp(Rational::new(0, 1)), Ok(Rational::new(1, 1)));
+    }
+}
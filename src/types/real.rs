@@ -1,75 +1,309 @@
-use crate::operations::{Add, Exp, Mul, Sub, TypeAdd, TypeExp, TypeMul, TypeSub};
-use super::{zahl::Zahl, MyFrom, MyInto, Types, Wrapper};
+use std::{cell::Cell, str::FromStr};
 
-#[derive(Debug, Clone)]
-pub struct Real(f32);
+use dashu_float::{ops::Abs, DBig};
+
+use crate::{operations::{Add, Div, Exp, Mul, Sub}, Error};
+use super::Wrapper;
+
+thread_local! {
+    // Significant decimal digits of reals in the evaluation running on this thread,
+    // 0 computes with f64. The arithmetic traits take no context, so it is passed here
+    static PRECISION: Cell<usize> = const { Cell::new(0) };
+}
+
+// Restores the precision of the thread when the evaluation ends, also by unwinding
+struct Restore(usize);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        PRECISION.set(self.0);
+    }
+}
+
+// Runs the evaluation with reals of the number of significant decimal digits
+pub(crate) fn with_precision<T>(digits: usize, evaluation: impl FnOnce() -> T) -> T {
+    let _restore = Restore(PRECISION.replace(digits));
+    evaluation()
+}
+
+pub(crate) fn precision() -> usize {
+    PRECISION.get()
+}
+
+/// Approximate number, a double or a decimal of the precision of the numeric evaluation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Real {
+    Double(f64),
+    Decimal(DBig),
+}
 
 impl Real {
-    pub fn new(value: f32) -> Self {
-        Self(value)
+    pub fn new(value: f64) -> Self {
+        Real::Double(value)
     }
 
-    pub fn get(&self) -> f32 {
-        self.0
+    /// The fraction as a real of the current precision
+    pub fn fraction(numerator: i64, denominator: i64) -> Self {
+        match precision() {
+            0 => Real::Double(numerator as f64 / denominator as f64),
+            digits => Real::Decimal(DBig::from(numerator).with_precision(digits).value() / DBig::from(denominator)),
+        }
     }
-}
 
-pub type WrappedReal = Wrapper<Real>;
+    /// Nearest f64, decimals out of its range become infinite
+    pub fn get(&self) -> f64 {
+        match self {
+            Real::Double(value) => *value,
+            Real::Decimal(decimal) => decimal.to_f64().value(),
+        }
+    }
 
-impl WrappedReal {
-    pub fn new(value: f32) -> Self {
-        Self::Constant(Real(value))
+    // The value with the digits of the current precision, None when computing with f64
+    fn decimal(&self) -> Option<DBig> {
+        let digits = precision();
+
+        let decimal = match self {
+            _ if digits == 0 => return None,
+            Real::Decimal(decimal) => decimal.clone(),
+            // The shortest representation, so 0.1 is the decimal 0.1 and not the nearest double
+            Real::Double(value) => DBig::from_str(&value.to_string()).ok()?,
+        };
+
+        Some(decimal.with_precision(digits).value())
     }
 
-    pub fn get_type(&self) -> &str {
-        "Real"
+    pub fn sqrt(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) if decimal < DBig::ZERO => Err(Error::domain(format!("sqrt({self})"))),
+            Some(decimal) => Ok(Real::Decimal(decimal.context().sqrt(decimal.repr()).value())),
+            None if self.get() < 0.0 => Err(Error::domain(format!("sqrt({self})"))),
+            None => finite(self.get().sqrt(), || format!("sqrt({self})")),
+        }
+    }
+
+    pub fn ln(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) if decimal <= DBig::ZERO => Err(Error::domain(format!("ln({self})"))),
+            Some(decimal) => Ok(Real::Decimal(decimal.ln())),
+            None if self.get() <= 0.0 => Err(Error::domain(format!("ln({self})"))),
+            None => finite(self.get().ln(), || format!("ln({self})")),
+        }
+    }
+
+    pub fn exp(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) => Ok(Real::Decimal(decimal.exp())),
+            None => finite(self.get().exp(), || format!("exp({self})")),
+        }
+    }
+
+    pub fn sin(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) => Ok(Real::Decimal(sine_cosine(&decimal, precision()).0)),
+            None => finite(self.get().sin(), || format!("sin({self})")),
+        }
+    }
+
+    pub fn cos(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) => Ok(Real::Decimal(sine_cosine(&decimal, precision()).1)),
+            None => finite(self.get().cos(), || format!("cos({self})")),
+        }
+    }
+
+    pub fn tan(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) => match sine_cosine(&decimal, precision() + 10) {
+                (_, cosine) if cosine == DBig::ZERO => Err(Error::domain(format!("tan({self})"))),
+                (sine, cosine) => Ok(Real::Decimal((sine / cosine).with_precision(precision()).value())),
+            },
+            None => finite(self.get().tan(), || format!("tan({self})")),
+        }
+    }
+
+    /// Angle of the point (x, self) in (-pi, pi], 0 for the origin
+    pub fn atan2(self, x: Real) -> Result<Real, Error> {
+        let (Some(y), Some(x)) = (self.decimal(), x.decimal()) else {
+            return finite(self.get().atan2(x.get()), || format!("atan2({self}, {x})"));
+        };
+
+        let digits = precision();
+        let half_turn = || pi(digits + 10);
+
+        let angle = if x > DBig::ZERO {
+            arctan(y / x, digits + 10)
+        } else if x < DBig::ZERO && y < DBig::ZERO {
+            arctan(y / x, digits + 10) - half_turn()
+        } else if x < DBig::ZERO {
+            arctan(y / x, digits + 10) + half_turn()
+        } else if y == DBig::ZERO {
+            DBig::ZERO
+        } else {
+            half_turn() / DBig::from(2) * y.signum()
+        };
+
+        Ok(Real::Decimal(angle.with_precision(digits).value()))
+    }
+
+    /// 1 / x, an error for 0
+    pub fn reciprocal(self) -> Result<Real, Error> {
+        match self.decimal() {
+            Some(decimal) if decimal == DBig::ZERO => Err(Error::division_by_zero()),
+            Some(decimal) => Ok(Real::Decimal(DBig::ONE.with_precision(precision()).value() / decimal)),
+            None if self.get() == 0.0 => Err(Error::division_by_zero()),
+            None => finite(1.0 / self.get(), || format!("1 / {self}")),
+        }
+    }
+
+    /// Ratio of a circle's circumference to its diameter in the current precision
+    pub fn pi() -> Real {
+        match precision() {
+            0 => Real::new(std::f64::consts::PI),
+            digits => Real::Decimal(pi(digits)),
+        }
+    }
+
+    /// Euler's number in the current precision
+    pub fn e() -> Real {
+        match precision() {
+            0 => Real::new(std::f64::consts::E),
+            digits => Real::Decimal(DBig::ONE.with_precision(digits).value().exp()),
+        }
     }
+
 }
 
-impl<From: MyInto<Zahl>> MyFrom<From> for Real {
-    fn my_from(from: From) -> Real {
-        Self::new(from.my_into().get() as f32)
+// Machin's formula pi = 16 * atan(1/5) - 4 * atan(1/239), with guard digits against rounding
+fn pi(digits: usize) -> DBig {
+    let guarded = digits + 10;
+    let pi = DBig::from(16) * arctan_inverse(5, guarded) - DBig::from(4) * arctan_inverse(239, guarded);
+
+    pi.with_precision(digits).value()
+}
+
+// atan x by its Taylor series, after halving the angle with atan x = 2 atan(x / (1 + sqrt(1 + x²)))
+// until x is small enough for the series to converge quickly
+fn arctan(x: DBig, digits: usize) -> DBig {
+    let guarded = digits + 10;
+    let one = DBig::ONE.with_precision(guarded).value();
+    let small = DBig::from_parts(1.into(), -1);
+
+    let mut x = x.with_precision(guarded).value();
+    let mut halvings = 0;
+
+    while x.clone().abs() > small {
+        let root = one.clone() + x.clone() * x.clone();
+        x /= one.clone() + root.context().sqrt(root.repr()).value();
+        halvings += 1;
+    }
+
+    let limit = DBig::from_parts(1.into(), -(guarded as isize));
+    let square = x.clone() * x.clone();
+    let mut power = x;
+    let mut sum = DBig::ZERO.with_precision(guarded).value();
+    let mut k = 0;
+
+    while power.clone().abs() > limit {
+        let term = power.clone() / DBig::from(2 * k + 1);
+        sum = if k % 2 == 0 { sum + term } else { sum - term };
+
+        power *= square.clone();
+        k += 1;
     }
+
+    (sum * DBig::from(1i64 << halvings)).with_precision(digits).value()
 }
 
-impl MyFrom<Real> for Real {
-    fn my_from(from: Real) -> Self {
-        from
+// sin x and cos x by their Taylor series, after reducing x to [-pi, pi].
+// The reduction cancels the digits of the integer part, so they are added as guard digits
+fn sine_cosine(x: &DBig, digits: usize) -> (DBig, DBig) {
+    let guarded = digits + 10 + x.to_f64().value().abs().log10().max(0.0) as usize;
+    let x = x.clone().with_precision(guarded).value();
+
+    let tau = DBig::from(2) * pi(guarded);
+    let x = x.clone() - (x / tau.clone()).round() * tau;
+
+    let limit = DBig::from_parts(1.into(), -(guarded as isize));
+    let square = x.clone() * x.clone();
+
+    // Each term is the previous one times -x² / ((n + 1)(n + 2)), starting with x for sin and 1 for cos
+    let series = |first: DBig, mut n: i64| {
+        let mut term = first;
+        let mut sum = DBig::ZERO.with_precision(guarded).value();
+
+        while term.clone().abs() > limit {
+            sum += term.clone();
+            term = -(term * square.clone()) / DBig::from((n + 1) * (n + 2));
+            n += 2;
+        }
+
+        sum.with_precision(digits).value()
+    };
+
+    (series(x.clone(), 1), series(DBig::ONE.with_precision(guarded).value(), 0))
+}
+
+// atan(1 / x) = 1/x - 1/(3x³) + 1/(5x⁵) - ..., summed until the terms are below the digits
+fn arctan_inverse(x: i64, digits: usize) -> DBig {
+    let limit = DBig::from_parts(1.into(), -(digits as isize));
+    let square = DBig::from(x * x);
+
+    let mut power = DBig::ONE.with_precision(digits).value() / DBig::from(x);
+    let mut sum = DBig::ZERO.with_precision(digits).value();
+    let mut k = 0;
+
+    while power > limit {
+        let term = power.clone() / DBig::from(2 * k + 1);
+        sum = if k % 2 == 0 { sum + term } else { sum - term };
+
+        power /= square.clone();
+        k += 1;
     }
+
+    sum
 }
 
-impl Add for Real {
-    type Output = Real;
+pub type WrappedReal = Wrapper<Real>;
 
-    fn add(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Real::new(self.get() + rhs.get()))
+impl WrappedReal {
+    pub fn new(value: f64) -> Self {
+        Self::Constant(Real::new(value))
+    }
+
+    pub fn get_type(&self) -> &str {
+        "Real"
     }
 }
 
-impl TypeAdd for WrappedReal {
-    fn type_add(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.add(rhs.my_into()),
-            Types::Zahl(rhs) => self.add(rhs.my_into()),
-            Types::Real(rhs) => self.add(rhs),
-        }
+// Non finite results of finite operands are errors instead of values
+pub(super) fn finite(value: f64, operation: impl FnOnce() -> String) -> Result<Real, Error> {
+    if value.is_nan() {
+        Err(Error::domain(operation()))
+    } else if value.is_infinite() {
+        Err(Error::overflow(operation()))
+    } else {
+        Ok(Real::new(value))
     }
 }
 
-impl Sub for Real {
+impl Add for Real {
     type Output = Real;
 
-    fn sub(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Real::new(self.get() - rhs.get()))
+    fn add(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self.decimal(), rhs.decimal()) {
+            (Some(left), Some(right)) => Ok(Real::Decimal(left + right)),
+            _ => finite(self.get() + rhs.get(), || format!("{self} + {rhs}")),
+        }
     }
 }
 
-impl TypeSub for WrappedReal {
-    fn type_sub(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.sub(rhs.my_into()),
-            Types::Zahl(rhs) => self.sub(rhs.my_into()),
-            Types::Real(rhs) => self.sub(rhs),
+impl Sub for Real {
+    type Output = Real;
+
+    fn sub(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self.decimal(), rhs.decimal()) {
+            (Some(left), Some(right)) => Ok(Real::Decimal(left - right)),
+            _ => finite(self.get() - rhs.get(), || format!("{self} - {rhs}")),
         }
     }
 }
@@ -77,42 +311,46 @@ impl TypeSub for WrappedReal {
 impl Mul for Real {
     type Output = Real;
 
-    fn mul(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Real::new(self.get() * rhs.get()))
+    fn mul(self, rhs: Self) -> Result<Self::Output, Error> {
+        match (self.decimal(), rhs.decimal()) {
+            (Some(left), Some(right)) => Ok(Real::Decimal(left * right)),
+            _ => finite(self.get() * rhs.get(), || format!("{self} * {rhs}")),
+        }
     }
 }
 
-impl TypeMul for WrappedReal {
-    fn type_mul(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.mul(rhs.my_into()),
-            Types::Zahl(rhs) => self.mul(rhs.my_into()),
-            Types::Real(rhs) => self.mul(rhs),
-        }
+impl Div for Real {
+    type Output = Real;
+
+    fn div(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.mul(rhs.reciprocal()?)
     }
 }
 
 impl Exp for Real {
     type Output = Real;
 
-    fn exp(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Real::new((self.get()).powf(rhs.get())))
-    }
-}
+    fn exp(self, rhs: Self) -> Result<Self::Output, Error> {
+        if self.get() == 0.0 && rhs.get() < 0.0 {
+            return Err(Error::division_by_zero());
+        }
 
-impl TypeExp for WrappedReal {
-    fn type_exp(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.exp(rhs.my_into()),
-            Types::Zahl(rhs) => self.exp(rhs.my_into()),
-            Types::Real(rhs) => self.exp(rhs),
+        match (self.decimal(), rhs.decimal()) {
+            // Integer powers are exact and allow negative bases, other powers go through exp and ln
+            (Some(left), Some(right)) if right.fract() == DBig::ZERO => Ok(Real::Decimal(left.powi(right.to_int().value()))),
+            (Some(left), Some(right)) if left > DBig::ZERO => Ok(Real::Decimal(left.powf(&right))),
+            (Some(left), Some(_)) if left == DBig::ZERO => Ok(Real::Decimal(left)),
+            (Some(_), Some(_)) => Err(Error::domain(format!("{self} ** {rhs}"))),
+            _ => finite(self.get().powf(rhs.get()), || format!("{self} ** {rhs}")),
         }
     }
 }
 
-
 impl std::fmt::Display for Real {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.get())
+        match self {
+            Real::Double(value) => write!(f, "{value}"),
+            Real::Decimal(decimal) => write!(f, "{decimal}"),
+        }
     }
-}
\ No newline at end of file
+}
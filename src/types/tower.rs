@@ -0,0 +1,174 @@
+//! Numeric tower, every type converts into the types above it.
+
+use super::{Complex, Rational, Real, Types, Wrapper, WrappedComplex, WrappedNatural, WrappedRational, WrappedReal, WrappedZahl, Zahl};
+
+/// Position of a number type in the tower
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Natural,
+    Zahl,
+    Rational,
+    Real,
+    Complex,
+}
+
+/// Both operands of a binary operation converted to their least common type
+pub enum Common {
+    Natural(WrappedNatural, WrappedNatural),
+    Zahl(WrappedZahl, WrappedZahl),
+    Rational(WrappedRational, WrappedRational),
+    Real(WrappedReal, WrappedReal),
+    Complex(WrappedComplex, WrappedComplex),
+}
+
+/// Runs the same code for the operands of any type of a [`Common`],
+/// which must implement the operation for every level
+macro_rules! dispatch {
+    ($common:expr, |$left:ident, $right:ident| $body:expr) => {
+        match $common {
+            $crate::Common::Natural($left, $right) => $body,
+            $crate::Common::Zahl($left, $right) => $body,
+            $crate::Common::Rational($left, $right) => $body,
+            $crate::Common::Real($left, $right) => $body,
+            $crate::Common::Complex($left, $right) => $body,
+        }
+    };
+}
+pub(crate) use dispatch;
+
+impl<T> Wrapper<T> {
+    /// Converts the constant, variables and expressions keep their name or tree
+    pub fn map<O>(self, f: impl FnOnce(T) -> O) -> Wrapper<O> {
+        match self {
+            Wrapper::Constant(value) => Wrapper::Constant(f(value)),
+            Wrapper::Variable(name) => Wrapper::Variable(name),
+            Wrapper::Expression(expression) => Wrapper::Expression(expression),
+        }
+    }
+}
+
+impl Types {
+    pub fn level(&self) -> Level {
+        match self {
+            Types::Natural(_) => Level::Natural,
+            Types::Zahl(_) => Level::Zahl,
+            Types::Rational(_) => Level::Rational,
+            Types::Real(_) => Level::Real,
+            Types::Complex(_) => Level::Complex,
+        }
+    }
+
+    // Same value as the type one level up, the only conversions of the tower.
+    // Naturals above i32::MAX skip the Zahl level
+    fn raise(self) -> Types {
+        match self {
+            Types::Natural(Wrapper::Constant(natural)) => match i32::try_from(natural.get()) {
+                Ok(zahl) => Types::Zahl(Wrapper::Constant(Zahl::new(zahl))),
+                Err(_) => Types::Rational(Wrapper::Constant(Rational::new(natural.get().into(), 1))),
+            },
+            Types::Natural(Wrapper::Variable(name)) => Types::Zahl(Wrapper::Variable(name)),
+            Types::Natural(Wrapper::Expression(expression)) => Types::Zahl(Wrapper::Expression(expression)),
+            Types::Zahl(zahl) => Types::Rational(zahl.map(|zahl| Rational::new(zahl.get().into(), 1))),
+            Types::Rational(rational) => Types::Real(rational.map(|rational| Real::fraction(rational.numerator(), rational.denominator()))),
+            Types::Real(real) => Types::Complex(real.map(|real| Complex::Approximate(real, Real::new(0.0)))),
+            Types::Complex(complex) => Types::Complex(complex),
+        }
+    }
+
+    /// Same value as a type of at least the level
+    pub fn promote(self, level: Level) -> Types {
+        let mut types = self;
+
+        while types.level() < level {
+            types = match types {
+                // Fractions skip the reals, so complex numbers with fractional parts stay exact
+                Types::Rational(rational) if level == Level::Complex => {
+                    Types::Complex(rational.map(|rational| Complex::Exact(rational, Rational::new(0, 1))))
+                }
+                types => types.raise(),
+            };
+        }
+
+        types
+    }
+
+    /// Promotes both operands to the higher of their levels
+    pub fn common(self, other: Types) -> Common {
+        let level = self.level().max(other.level());
+        let (left, right) = (self.promote(level), other.promote(level));
+
+        // Large naturals may have skipped past the level of the other operand
+        let level = left.level().max(right.level());
+
+        match (left.promote(level), right.promote(level)) {
+            (Types::Natural(left), Types::Natural(right)) => Common::Natural(left, right),
+            (Types::Zahl(left), Types::Zahl(right)) => Common::Zahl(left, right),
+            (Types::Rational(left), Types::Rational(right)) => Common::Rational(left, right),
+            (Types::Real(left), Types::Real(right)) => Common::Real(left, right),
+            (Types::Complex(left), Types::Complex(right)) => Common::Complex(left, right),
+            _ => unreachable!("operands are promoted to the same level"),
+        }
+    }
+
+    /// Complex numbers without imaginary part as the type below
+    pub fn demote(self) -> Types {
+        match self {
+            Types::Complex(Wrapper::Constant(Complex::Exact(real, imaginary))) if imaginary.numerator() == 0 => {
+                Types::Rational(Wrapper::Constant(real))
+            }
+            Types::Complex(Wrapper::Constant(Complex::Approximate(real, imaginary))) if imaginary.get() == 0.0 => {
+                Types::Real(Wrapper::Constant(real))
+            }
+            types => types,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, Expressions};
+    use super::*;
+
+    fn evaluate(expression: &str) -> (String, String) {
+        let result = parse(expression).unwrap().solve().unwrap();
+        (result.to_string(), result.get_type().to_string())
+    }
+
+    #[test]
+    fn promotes_to_the_higher_level() {
+        assert_eq!(evaluate("1 + 2"), ("3".to_string(), "Natural".to_string()));
+        assert_eq!(evaluate("1 - 2"), ("-1".to_string(), "Zahl".to_string()));
+        assert_eq!(evaluate("(0 - 1) + 1/2"), ("-1/2".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("1/2 + 0.25"), ("0.75".to_string(), "Real".to_string()));
+        assert_eq!(evaluate("1/2 + i"), ("1/2 + i".to_string(), "Complex".to_string()));
+    }
+
+    #[test]
+    fn naturals_above_the_zahl_range_stay_exact() {
+        assert_eq!(evaluate("2147483647 * (0 - 1)"), ("-2147483647".to_string(), "Zahl".to_string()));
+        assert_eq!(evaluate("2147483648 * (0 - 1)"), ("-2147483648".to_string(), "Rational".to_string()));
+        assert_eq!(evaluate("3000000000 + 0.5").0, "3000000000.5");
+        assert_eq!(evaluate("3000000000 * 1/2").0, "1500000000");
+        assert_eq!(evaluate("4294967295 * 1/2").0, "4294967295/2");
+        assert_eq!(evaluate("4294967295 + (0 - 1)").0, "4294967294");
+        assert_eq!(evaluate("3000000000 * i").0, "3000000000 * i");
+    }
+
+    #[test]
+    fn variables_keep_their_kind() {
+        let promoted = Types::Natural(Wrapper::Variable("n".to_string())).promote(Level::Real);
+        assert_eq!(promoted, Types::Real(Wrapper::Variable("n".to_string())));
+
+        let expression: Expressions = WrappedNatural::new(1).into();
+        let promoted = Types::Natural(Wrapper::Expression(expression.clone())).promote(Level::Zahl);
+        assert_eq!(promoted, Types::Zahl(Wrapper::Expression(expression)));
+    }
+
+    #[test]
+    fn complex_numbers_without_imaginary_part_are_demoted() {
+        assert_eq!(Types::Complex(Wrapper::Constant(Complex::Exact(Rational::new(1, 2), Rational::new(0, 1)))).demote().get_type(), "Rational");
+        assert_eq!(Types::Complex(Wrapper::Constant(Complex::approximate(0.5, 0.0))).demote().get_type(), "Real");
+        assert_eq!(Types::Complex(Wrapper::Constant(Complex::unit())).demote().get_type(), "Complex");
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{operations::{Add, Exp, Mul, Sub, TypeAdd, TypeExp, TypeMul, TypeSub}, MyInto};
-use super::{natural::Natural, real::WrappedReal, MyFrom, Types, Wrapper};
+use crate::{operations::{Add, Exp, Mul, Sub}, Error};
+use super::Wrapper;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Zahl(i32);
 
 impl Zahl {
@@ -14,18 +14,6 @@ impl Zahl {
     }
 }
 
-impl<From: MyInto<Natural>> MyFrom<From> for Zahl {
-    fn my_from(from: From) -> Zahl {
-        Self::new(from.my_into().get() as i32)
-    }
-}
-
-impl MyFrom<Zahl> for Zahl {
-    fn my_from(from: Zahl) -> Self {
-        from
-    }
-}
-
 pub type WrappedZahl = Wrapper<Zahl>;
 
 impl WrappedZahl {
@@ -41,64 +29,34 @@ impl WrappedZahl {
 impl Add for Zahl {
     type Output = Zahl;
 
-    fn add(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Zahl::new(self.get() + rhs.get()))
-    }
-}
-
-impl TypeAdd for WrappedZahl {
-    fn type_add(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.add(rhs.my_into()),
-            Types::Zahl(rhs) => self.add(rhs),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).add(rhs),
-        }
+    fn add(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_add(rhs.get()).map(Zahl::new).ok_or(Error::overflow(format!("{self} + {rhs}")))
     }
 }
 
 impl Sub for Zahl {
     type Output = Zahl;
 
-    fn sub(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Zahl::new(self.get() - rhs.get()))
-    }
-}
-
-impl TypeSub for WrappedZahl {
-    fn type_sub(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.sub(rhs.my_into()),
-            Types::Zahl(rhs) => self.sub(rhs),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).sub(rhs),
-        }
+    fn sub(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_sub(rhs.get()).map(Zahl::new).ok_or(Error::overflow(format!("{self} - {rhs}")))
     }
 }
 
 impl Mul for Zahl {
     type Output = Zahl;
 
-    fn mul(self, rhs: Self) -> Result<Self::Output, String> {
-        Ok(Zahl::new(self.get() * rhs.get()))
+    fn mul(self, rhs: Self) -> Result<Self::Output, Error> {
+        self.get().checked_mul(rhs.get()).map(Zahl::new).ok_or(Error::overflow(format!("{self} * {rhs}")))
     }
 }
 
-impl TypeMul for WrappedZahl {
-    fn type_mul(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => self.mul(rhs.my_into()),
-            Types::Zahl(rhs) => self.mul(rhs),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).mul(rhs),
-        }
-    }
-}
+// Negative powers of integers are fractions, their bases are promoted to rationals first
+impl Exp for Zahl {
+    type Output = Zahl;
 
-impl TypeExp for WrappedZahl {
-    fn type_exp(self, rhs: Types) -> Result<Types, String> {
-        match rhs {
-            Types::Natural(rhs) => MyInto::<WrappedReal>::my_into(self).exp(MyInto::<WrappedReal>::my_into(rhs)),
-            Types::Zahl(rhs) => MyInto::<WrappedReal>::my_into(self).exp(MyInto::<WrappedReal>::my_into(rhs)),
-            Types::Real(rhs) => MyInto::<WrappedReal>::my_into(self).exp(rhs),
-        }
+    fn exp(self, rhs: Self) -> Result<Self::Output, Error> {
+        let exponent = u32::try_from(rhs.get()).map_err(|_| Error::type_mismatch(format!("{self} ** {rhs} is not an integer")))?;
+        self.get().checked_pow(exponent).map(Zahl::new).ok_or(Error::overflow(format!("({self}) ** {rhs}")))
     }
 }
 
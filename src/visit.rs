@@ -0,0 +1,166 @@
+//! Generic traversals of expression trees.
+//!
+//! The traits recurse through [`Expressions::get_children`] and
+//! [`Expressions::map_children`] by default, so implementations only handle
+//! the nodes they care about and new operations need no changes here.
+
+use std::sync::Arc;
+
+use crate::{operations::{Operation, OperationTrait}, Expressions, InnerExpressions, Types};
+
+/// Read only traversal, parents are visited before their children
+pub trait Visitor {
+    fn visit(&mut self, expression: &Expressions) {
+        walk(self, expression);
+    }
+
+    fn visit_type(&mut self, _types: &Types) {}
+
+    fn visit_operation(&mut self, operation: &Operation) {
+        walk_operation(self, operation);
+    }
+}
+
+/// Dispatches to the visit method of the node
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expressions) {
+    match expression.0.as_ref() {
+        InnerExpressions::Type(types) => visitor.visit_type(types),
+        InnerExpressions::Operation(operation) => visitor.visit_operation(operation),
+    }
+}
+
+/// Visits every child of the operation
+pub fn walk_operation<V: Visitor + ?Sized>(visitor: &mut V, operation: &Operation) {
+    for child in operation.get_children().iter() {
+        visitor.visit(child);
+    }
+}
+
+/// Rewriting traversal, the visited expression can be replaced in place
+pub trait VisitorMut {
+    fn visit_mut(&mut self, expression: &mut Expressions) {
+        walk_mut(self, expression);
+    }
+}
+
+/// Visits every child, rebuilding the expression only if a child was replaced
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expressions) {
+    let mut changed = false;
+
+    let rebuilt = expression.map_children(&mut |_, child| {
+        let mut visited = child.clone();
+        visitor.visit_mut(&mut visited);
+        changed |= !Arc::ptr_eq(&visited.0, &child.0);
+        visited
+    });
+
+    if changed {
+        *expression = rebuilt;
+    }
+}
+
+/// Bottom up computation of a value per node from the values of its children
+pub trait Fold {
+    type Output;
+
+    fn fold(&mut self, expression: &Expressions) -> Self::Output {
+        match expression.0.as_ref() {
+            InnerExpressions::Type(types) => self.fold_type(types),
+            InnerExpressions::Operation(operation) => {
+                let children = operation.get_children().iter().map(|child| self.fold(child)).collect();
+                self.fold_operation(operation, children)
+            }
+        }
+    }
+
+    fn fold_type(&mut self, types: &Types) -> Self::Output;
+
+    /// Combines the values of the children, in the order of `get_children`
+    fn fold_operation(&mut self, operation: &Operation, children: Vec<Self::Output>) -> Self::Output;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::parse, WrappedNatural};
+    use super::*;
+
+    // Counts the nodes, types are leaves
+    #[derive(Default)]
+    struct Count {
+        types: usize,
+        operations: usize,
+    }
+
+    impl Visitor for Count {
+        fn visit_type(&mut self, _types: &Types) {
+            self.types += 1;
+        }
+
+        fn visit_operation(&mut self, operation: &Operation) {
+            self.operations += 1;
+            walk_operation(self, operation);
+        }
+    }
+
+    // Replaces the variable x by 2
+    struct ReplaceX;
+
+    impl VisitorMut for ReplaceX {
+        fn visit_mut(&mut self, expression: &mut Expressions) {
+            if expression.get_variable().as_deref() == Some("x") {
+                *expression = WrappedNatural::new(2).into();
+            } else {
+                walk_mut(self, expression);
+            }
+        }
+    }
+
+    // Height of the tree
+    struct Depth;
+
+    impl Fold for Depth {
+        type Output = usize;
+
+        fn fold_type(&mut self, _types: &Types) -> usize {
+            1
+        }
+
+        fn fold_operation(&mut self, _operation: &Operation, children: Vec<usize>) -> usize {
+            1 + children.into_iter().max().unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn visitor_reaches_every_node() {
+        let mut count = Count::default();
+        count.visit(&parse("sin(x) + (a * b) ** 2").unwrap());
+
+        assert_eq!((count.operations, count.types), (4, 4));
+    }
+
+    #[test]
+    fn visitor_mut_rebuilds_only_changed_parents() {
+        let expression = parse("(x + 1) * (a + b)").unwrap();
+        let mut rewritten = expression.clone();
+        ReplaceX.visit_mut(&mut rewritten);
+
+        assert_eq!(rewritten.to_string(), "(2 + 1) * (a + b)");
+        assert!(Arc::ptr_eq(&rewritten.get_children()[1].0, &expression.get_children()[1].0));
+    }
+
+    #[test]
+    fn visitor_mut_keeps_unchanged_expressions() {
+        let expression = parse("a + b").unwrap();
+        let mut rewritten = expression.clone();
+        ReplaceX.visit_mut(&mut rewritten);
+
+        assert!(Arc::ptr_eq(&rewritten.0, &expression.0));
+    }
+
+    #[test]
+    fn fold_combines_children_bottom_up() {
+        assert_eq!(Depth.fold(&parse("x").unwrap()), 1);
+        assert_eq!(Depth.fold(&parse("1 + 2 * (3 + x)").unwrap()), 4);
+        assert_eq!(Depth.fold(&parse("f(1, g(2))").unwrap()), 3);
+    }
+}
@@ -0,0 +1,55 @@
+use ruth::{
+    commands::run_command, definitions::Definition, latex, parser, rules, state::State, Addition, BinaryOperation, ErrorKind,
+    Exponentiation, Expressions, WrappedNatural, WrappedReal,
+};
+
+#[test]
+fn expressions_are_built_from_constructors() {
+    let expression: Expressions = Exponentiation::new(
+        Addition::new(WrappedReal::new_variable("b").into(), WrappedReal::new_variable("c").into()).into(),
+        WrappedNatural::new(2).into(),
+    ).into();
+
+    assert!(expression == parser::parse("(b + c) ** 2").unwrap());
+    assert_eq!(latex::to_latex(&expression), "\\left(b + c\\right)^{2}");
+}
+
+#[test]
+fn rules_are_listed_and_applied() {
+    let mut state = State::new(parser::parse("x * (a + b)").unwrap());
+    let matches = rules::find_all_rules(&state.selection(), &state.definitions);
+
+    state.apply(matches[0].result(), matches[0].name);
+
+    assert_eq!(state.current.to_string(), "x * b + x * a");
+    assert_eq!(state.history.len(), 2);
+}
+
+#[test]
+fn definitions_are_unfolded_by_rules() {
+    let definition = Definition::parse("f(x) = x ** 2").unwrap();
+    let matches = rules::find_all_rules(&parser::parse("f(3)").unwrap(), &[definition]);
+
+    let unfolded = matches.iter().find(|found| found.name == "Unfold").unwrap();
+    assert_eq!(unfolded.result().solve().unwrap().to_string(), "9");
+}
+
+#[test]
+fn evaluation_is_exact_or_numeric() {
+    let third = parser::parse("1/3 + 0").unwrap().solve().unwrap();
+    assert_eq!((third.to_string(), third.get_type()), ("1/3".to_string(), "Rational"));
+
+    assert_eq!(parser::parse("sqrt(2)").unwrap().solve().unwrap().to_string(), "sqrt(2)");
+    assert_eq!(parser::parse("sqrt(2)").unwrap().numeric().unwrap().to_string(), "1.4142135623730951");
+}
+
+#[test]
+fn commands_run_on_a_state() {
+    let mut state = State::new(parser::parse("x").unwrap());
+
+    run_command(&mut state, "load 0 * y + 1 * z").unwrap();
+    run_command(&mut state, "simplify").unwrap();
+
+    assert_eq!(state.current.to_string(), "z");
+    assert!(matches!(run_command(&mut state, "load (").unwrap_err().kind, ErrorKind::Parse(_)));
+}
@@ -0,0 +1,76 @@
+use std::process::{Command, Output};
+
+fn ruth(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ruth")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn subcommands_print_their_result() {
+    let cases = [
+        (["eval", "2 ** 10"], "1024\n"),
+        (["numeric", "sqrt(4)"], "2\n"),
+        (["simplify", "0 * x + 1 * y"], "y\n"),
+        (["rules", "x * (a + b)"], "0: Distributivity: x * (a + b) = x * a + x * b: x * b + x * a\n"),
+        (["latex", "1/2 + x"], "\\frac{1}{2} + x\n"),
+    ];
+
+    for (args, expected) in cases {
+        let output = ruth(&args);
+
+        assert!(output.status.success(), "{args:?}: {}", stderr(&output));
+        assert_eq!(stdout(&output), expected, "{args:?}");
+    }
+}
+
+#[test]
+fn the_expression_may_span_several_arguments() {
+    let output = ruth(&["eval", "2", "**", "10"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "1024\n");
+}
+
+#[test]
+fn usage_errors_exit_with_2() {
+    for args in [&["eval"][..], &["frobnicate", "1"], &["--script"]] {
+        let output = ruth(args);
+
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr(&output).starts_with("Usage: ruth"), "{args:?}");
+    }
+}
+
+#[test]
+fn parse_errors_exit_with_3() {
+    for expression in ["1 +", "sin(1", "1 / 0"] {
+        let output = ruth(&["eval", expression]);
+
+        assert_eq!(output.status.code(), Some(3), "{expression}");
+        assert!(stderr(&output).starts_with("Invalid expression: "), "{expression}");
+        assert_eq!(stdout(&output), "");
+    }
+}
+
+#[test]
+fn evaluation_errors_exit_with_4() {
+    let cases = [
+        (["eval", "x + 1"], "Could not evaluate: Unbound variable: x\n"),
+        (["eval", "0 ** (0 - 1)"], "Could not evaluate: Division by zero\n"),
+        (["numeric", "ln(0)"], "Could not evaluate: Domain error: ln(0)\n"),
+    ];
+
+    for (args, expected) in cases {
+        let output = ruth(&args);
+
+        assert_eq!(output.status.code(), Some(4), "{args:?}");
+        assert_eq!(stderr(&output), expected, "{args:?}");
+    }
+}
@@ -0,0 +1,58 @@
+//! Decimal evaluation of the public API.
+
+use ruth::{commands::run_command, parser::parse, state::State};
+
+fn numeric(expression: &str, digits: usize) -> String {
+    parse(expression).unwrap().numeric_with_precision(digits).unwrap().to_string()
+}
+
+#[test]
+fn constants_and_roots() {
+    assert_eq!(numeric("pi", 50), "3.1415926535897932384626433832795028841971693993751");
+    assert_eq!(numeric("e", 50), "2.7182818284590452353602874713526624977572470937");
+    assert_eq!(numeric("sqrt(2)", 50), "1.4142135623730950488016887242096980785696718753769");
+}
+
+#[test]
+fn trigonometric_functions() {
+    assert_eq!(numeric("sin(1.0)", 50), "0.84147098480789650665250232163029899962256306079837");
+    assert_eq!(numeric("cos(1.0)", 50), "0.54030230586813971740093660744297660373231042061792");
+    assert_eq!(numeric("tan(1.0)", 50), "1.5574077246549022305069748074583601730872507723815");
+    assert_eq!(numeric("sin(100.0)", 50), "-0.50636564110975879365655761045978543206503272129066");
+}
+
+#[test]
+fn complex_arithmetic() {
+    assert_eq!(numeric("1/3 * i + 0.5", 40), "0.5 + 0.3333333333333333333333333333333333333333 * i");
+    assert_eq!(numeric("(0.5 + i) ** (0 - 1)", 40), "0.4 - 0.8 * i");
+    assert_eq!(numeric("sqrt(0.0 - 2)", 40), "1.41421356237309504880168872420969807857 * i");
+    assert_eq!(numeric("2 ** i", 40), "0.7692389013639721265783299936612707014409 + 0.6389612763136348011500329114647017842572 * i");
+}
+
+#[test]
+fn doubles_without_precision() {
+    assert_eq!(numeric("sin(1.0)", 0), "0.8414709848078965");
+    assert_eq!(numeric("(0 - 4) ** 0.5", 0), "2 * i");
+}
+
+#[test]
+fn precisions_are_independent() {
+    let threads: Vec<_> = [10, 20, 0].into_iter()
+        .map(|digits| std::thread::spawn(move || numeric("pi", digits)))
+        .collect();
+
+    let results: Vec<String> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+    assert_eq!(results, ["3.141592654", "3.1415926535897932385", "3.141592653589793"]);
+    assert_eq!(numeric("pi", 0), "3.141592653589793");
+}
+
+#[test]
+fn the_state_keeps_the_precision() {
+    let mut state = State::new(parse("sqrt(2)").unwrap());
+
+    run_command(&mut state, "precision 30").unwrap();
+    run_command(&mut state, "numeric").unwrap();
+
+    assert_eq!(state.precision, 30);
+    assert_eq!(state.current.to_string(), "1.41421356237309504880168872421");
+}
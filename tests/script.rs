@@ -0,0 +1,67 @@
+use std::{io::Write, process::{Command, Output, Stdio}};
+
+// Runs the binary with the script piped to stdin
+fn pipe(script: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ruth"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(script.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn piped_input_prints_only_requested_output() {
+    let output = pipe("load x ** 2\n# a comment\ndiff x\nsimplify\nlatex\n");
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "2 \\cdot x\n");
+}
+
+#[test]
+fn piped_input_stops_at_the_first_failing_command() {
+    let output = pipe("load x\nunknown\nlatex\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "Line 2: Unknown command: unknown\n");
+}
+
+#[test]
+fn empty_input_exits_successfully() {
+    let output = pipe("");
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn script_files_are_run() {
+    let path = std::env::temp_dir().join(format!("ruth-script-{}.ruth", std::process::id()));
+    std::fs::write(&path, "load 2 ** 10\nsimplify\nlatex\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ruth")).arg("--script").arg(&path).output().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "1024\n");
+}
+
+#[test]
+fn missing_script_files_are_errors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ruth")).args(["--script", "/nonexistent/script.ruth"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Could not read /nonexistent/script.ruth"));
+}